@@ -0,0 +1,188 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use std::collections::BTreeSet;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Expr, FnArg, Ident, ItemFn, LitStr, Pat, Result, ReturnType, Token, Type};
+
+// The arguments of the attribute, as those of format!.
+struct Args {
+    format: LitStr,
+    args: Vec<Arg>,
+}
+
+struct Arg {
+    name: Option<Ident>,
+    expr: Expr,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let format: LitStr = input.parse()?;
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name = if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+                let name = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Some(name)
+            } else {
+                None
+            };
+            let expr = input.parse()?;
+            args.push(Arg { name, expr });
+        }
+        Ok(Args { format, args })
+    }
+}
+
+pub fn expand(args: TokenStream, function: ItemFn) -> Result<TokenStream> {
+    if args.is_empty() {
+        return Err(Error::new_spanned(
+            &function.sig.ident,
+            "expected a context message, as in #[wallee::context(\"loading {id}\")]",
+        ));
+    }
+    let args: Args = syn::parse2(args)?;
+
+    if let ReturnType::Default = function.sig.output {
+        return Err(Error::new_spanned(
+            &function.sig,
+            "#[wallee::context] requires a function returning Result<T, wallee::Error>",
+        ));
+    }
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = &function;
+
+    // The message is only formatted if the function fails, after its body
+    // ran. Arguments passed by value or by mutable reference are moved into
+    // the body, so those that the message refers to are cloned beforehand.
+    let owned = owned_arguments(&function);
+    let mut captures = Vec::new();
+    let mut format_args = Vec::new();
+    for (i, arg) in args.args.iter().enumerate() {
+        let expr = &arg.expr;
+        let value = if mentions(quote!(#expr), &owned) {
+            let capture = format_ident!("__wallee_arg{}", i);
+            captures.push(quote!(let #capture = ::core::clone::Clone::clone(&(#expr));));
+            quote!(#capture)
+        } else {
+            quote!(#expr)
+        };
+        format_args.push(match &arg.name {
+            Some(name) => quote!(#name = #value),
+            None => value,
+        });
+    }
+    for name in inline_names(&args.format) {
+        let named = args.args.iter().any(|arg| arg.name.as_ref() == Some(&name));
+        if !named && owned.contains(&name.to_string()) {
+            let capture = format_ident!("__wallee_{}", name);
+            captures.push(quote!(let #capture = ::core::clone::Clone::clone(&#name);));
+            format_args.push(quote!(#name = #capture));
+        }
+    }
+    let format = &args.format;
+
+    // Location::caller() outside of a #[track_caller] function reports its own
+    // call site, which is given the span of the function name here. It is
+    // called from a nested function so that a #[track_caller] attribute on
+    // the annotated function does not make it report the caller's location.
+    let location = quote_spanned!(sig.ident.span()=> ::wallee::Location::caller());
+
+    // The body is passed through functions fixing its error type, so that `?`
+    // works without naming the return type, which may be `impl Trait`.
+    let result = if sig.asyncness.is_some() {
+        quote! {
+            ::wallee::__private::async_body(async move #block).await
+        }
+    } else {
+        // Called through a function taking FnOnce, so that the closure may
+        // return data borrowed from the arguments it moves in.
+        quote! {
+            ::wallee::__private::call_once(move || #block)
+        }
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            fn __wallee_location() -> &'static ::wallee::Location {
+                #location
+            }
+            #(#captures)*
+            ::wallee::__private::fn_context(
+                #result,
+                || ::wallee::__private::format!(#format #(, #format_args)*),
+                __wallee_location(),
+            )
+        }
+    })
+}
+
+// The names of the arguments that the body takes ownership of: those passed
+// by value, including `self`, and those passed by mutable reference.
+fn owned_arguments(function: &ItemFn) -> BTreeSet<String> {
+    let mut owned = BTreeSet::new();
+    for input in &function.sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                let shared = receiver.reference.is_some() && receiver.mutability.is_none();
+                if !shared {
+                    owned.insert("self".to_owned());
+                }
+            }
+            FnArg::Typed(arg) => {
+                let shared = match &*arg.ty {
+                    Type::Reference(reference) => reference.mutability.is_none(),
+                    _ => false,
+                };
+                if let Pat::Ident(pat) = &*arg.pat {
+                    if !shared {
+                        owned.insert(pat.ident.to_string());
+                    }
+                }
+            }
+        }
+    }
+    owned
+}
+
+fn mentions(tokens: TokenStream, names: &BTreeSet<String>) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => names.contains(&ident.to_string()),
+        TokenTree::Group(group) => mentions(group.stream(), names),
+        TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
+}
+
+// The variables named inline in the format string, as in "loading {id}".
+fn inline_names(format: &LitStr) -> Vec<Ident> {
+    let value = format.value();
+    let mut names = Vec::new();
+    let mut rest = value.as_str();
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let end = rest.find(['}', ':']).unwrap_or(rest.len());
+        let name = &rest[..end];
+        if name.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+            && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+            && !names.iter().any(|ident: &Ident| ident == name)
+        {
+            names.push(Ident::new(name, Span::call_site()));
+        }
+        rest = &rest[end..];
+    }
+    names
+}
@@ -0,0 +1,28 @@
+//! Derive macros for the [`wallee`] crate. Use them through the re-exports in
+//! `wallee` rather than depending on this crate directly.
+//!
+//! [`wallee`]: https://docs.rs/wallee
+
+extern crate proc_macro;
+
+mod context;
+mod locate;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn};
+
+#[proc_macro_derive(Locate, attributes(location, locate))]
+pub fn derive_locate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    locate::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn context(args: TokenStream, input: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(input as ItemFn);
+    context::expand(args.into(), function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
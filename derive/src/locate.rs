@@ -0,0 +1,262 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Error, Field, Fields, Ident, Index, Member, Result, Type, Visibility,
+};
+
+// One struct, or one variant of an enum, with its fields sorted out.
+struct Shape<'a> {
+    // Path used to construct and match the value: `Self` or `Self::Variant`.
+    path: TokenStream,
+    // Name of the generated constructor.
+    constructor: Ident,
+    fields: &'a Fields,
+    location: Member,
+    from: Option<Member>,
+}
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let shapes = match &input.data {
+        Data::Struct(data) => vec![shape(
+            quote!(Self),
+            new_ident(),
+            &data.fields,
+            &input.ident,
+        )?],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                let constructor = snake_case(ident);
+                shape(quote!(Self::#ident), constructor, &variant.fields, ident)
+            })
+            .collect::<Result<_>>()?,
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "unions are not supported by derive(Locate)",
+            ));
+        }
+    };
+
+    let ty = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let arms = shapes.iter().map(|shape| {
+        let path = &shape.path;
+        let location = &shape.location;
+        quote!(#path { #location: location, .. } => *location)
+    });
+
+    let constructors = if wants_constructors(input)? {
+        shapes.iter().map(|shape| constructor(shape, vis)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let froms = shapes.iter().filter_map(|shape| {
+        let from = shape.from.as_ref()?;
+        let source = shape.fields.members().zip(shape.fields).find(|(m, _)| m == from)?.1;
+        let source_ty = &source.ty;
+        let path = &shape.path;
+        let location = &shape.location;
+        Some(quote! {
+            impl #impl_generics ::core::convert::From<#source_ty> for #ty #ty_generics #where_clause {
+                #[cold]
+                #[track_caller]
+                fn from(source: #source_ty) -> Self {
+                    #path {
+                        #from: source,
+                        #location: ::wallee::Location::caller(),
+                    }
+                }
+            }
+        })
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::wallee::HasLocation for #ty #ty_generics #where_clause {
+            fn location(&self) -> &'static ::wallee::Location {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+
+        impl #impl_generics #ty #ty_generics #where_clause {
+            #(#constructors)*
+
+            // Picked by `wallee!(error)` over the kinds of other errors, so
+            // that the error keeps its location.
+            #[doc(hidden)]
+            #[inline]
+            #vis fn __wallee_kind(&self) -> ::wallee::__private::kind::MakeLocate {
+                ::wallee::__private::kind::MakeLocate
+            }
+        }
+
+        #(#froms)*
+    })
+}
+
+fn shape<'a>(
+    path: TokenStream,
+    constructor: Ident,
+    fields: &'a Fields,
+    name: &Ident,
+) -> Result<Shape<'a>> {
+    let mut location = None;
+    let mut from = None;
+    for (member, field) in fields.members().zip(fields) {
+        if is_location(field) {
+            if location.is_some() {
+                return Err(Error::new_spanned(field, "duplicate #[location] field"));
+            }
+            location = Some(member);
+        } else if is_from(field)? {
+            if from.is_some() {
+                return Err(Error::new_spanned(field, "duplicate #[locate(from)] field"));
+            }
+            from = Some(member);
+        }
+    }
+
+    let location = match location {
+        Some(location) => location,
+        None => {
+            return Err(Error::new_spanned(
+                name,
+                "derive(Locate) requires a `#[location] &'static wallee::Location` field",
+            ));
+        }
+    };
+
+    if from.is_some() && fields.len() != 2 {
+        return Err(Error::new_spanned(
+            name,
+            "#[locate(from)] requires no other fields than the #[location] field",
+        ));
+    }
+
+    Ok(Shape {
+        path,
+        constructor,
+        fields,
+        location,
+        from,
+    })
+}
+
+fn constructor(shape: &Shape, vis: &Visibility) -> TokenStream {
+    let path = &shape.path;
+    let name = &shape.constructor;
+    let location = &shape.location;
+
+    let mut params = Vec::new();
+    let mut inits = Vec::new();
+    for (member, field) in shape.fields.members().zip(shape.fields) {
+        if member == *location {
+            continue;
+        }
+        let arg = match &member {
+            Member::Named(ident) => ident.clone(),
+            Member::Unnamed(Index { index, .. }) => format_ident!("_{}", index),
+        };
+        let ty: &Type = &field.ty;
+        params.push(quote!(#arg: #ty));
+        inits.push(match &member {
+            Member::Named(_) => quote!(#arg),
+            Member::Unnamed(_) => quote!(#member: #arg),
+        });
+    }
+
+    quote! {
+        #[track_caller]
+        #[must_use]
+        #vis fn #name(#(#params),*) -> Self {
+            #path {
+                #(#inits,)*
+                #location: ::wallee::Location::caller(),
+            }
+        }
+    }
+}
+
+fn is_location(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("location"))
+}
+
+// The constructors are only generated on request, with `#[locate(constructors)]`
+// on the type, as their names may clash with methods of its own.
+fn wants_constructors(input: &DeriveInput) -> Result<bool> {
+    let mut constructors = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("locate") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("constructors") {
+                    constructors = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported locate attribute, expected `constructors`"))
+                }
+            })?;
+        }
+    }
+    Ok(constructors)
+}
+
+fn is_from(field: &Field) -> Result<bool> {
+    let mut from = false;
+    for attr in &field.attrs {
+        if attr.path().is_ident("locate") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("from") {
+                    from = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported locate attribute, expected `from`"))
+                }
+            })?;
+        }
+    }
+    Ok(from)
+}
+
+fn new_ident() -> Ident {
+    Ident::new("new", Span::call_site())
+}
+
+fn snake_case(ident: &Ident) -> Ident {
+    let name = ident.to_string();
+    let mut snake = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let boundary = match prev {
+                Some(prev) => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || prev.is_uppercase() && next.is_some_and(|next| next.is_lowercase())
+                }
+                None => false,
+            };
+            if boundary {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    match syn::parse_str::<Ident>(&snake) {
+        Ok(_) => Ident::new(&snake, ident.span()),
+        Err(_) => Ident::new_raw(&snake, ident.span()),
+    }
+}
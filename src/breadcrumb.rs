@@ -0,0 +1,82 @@
+use crate::error::ErrorImpl;
+use crate::location::Location;
+use crate::{Breadcrumb, Error};
+use alloc::collections::VecDeque;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Arguments;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::cell::RefCell;
+
+pub(crate) static CAPACITY: AtomicUsize = AtomicUsize::new(32);
+
+thread_local! {
+    static RING: RefCell<VecDeque<Breadcrumb>> = const { RefCell::new(VecDeque::new()) };
+}
+
+#[track_caller]
+pub fn record(args: Arguments) {
+    let capacity = CAPACITY.load(Ordering::Relaxed);
+    if capacity == 0 {
+        return;
+    }
+    let breadcrumb = Breadcrumb {
+        message: match args.as_str() {
+            Some(message) => Arc::from(message),
+            None => Arc::from(args.to_string()),
+        },
+        location: caller!(),
+    };
+    let _ = RING.try_with(|ring| {
+        let mut ring = ring.borrow_mut();
+        while ring.len() >= capacity {
+            ring.pop_front();
+        }
+        ring.push_back(breadcrumb);
+    });
+}
+
+// The breadcrumbs recorded on this thread, oldest first.
+pub(crate) fn snapshot() -> Vec<Breadcrumb> {
+    let capacity = CAPACITY.load(Ordering::Relaxed);
+    RING.try_with(|ring| {
+        let ring = ring.borrow();
+        let skip = ring.len().saturating_sub(capacity);
+        ring.iter().skip(skip).cloned().collect()
+    })
+    .unwrap_or_default()
+}
+
+impl Error {
+    /// The [breadcrumbs][crate::breadcrumb!] recorded on the thread where this
+    /// error originated, up to the moment it did, oldest first.
+    ///
+    /// ```
+    /// use wallee::{breadcrumb, wallee};
+    ///
+    /// let path = "config.toml";
+    /// breadcrumb!("opened {}", path);
+    /// breadcrumb!("parsed header");
+    /// let error = wallee!("unexpected end of file");
+    ///
+    /// let messages: Vec<&str> = error.breadcrumbs().iter().map(|b| b.message()).collect();
+    /// assert_eq!(messages, ["opened config.toml", "parsed header"]);
+    /// ```
+    pub fn breadcrumbs(&self) -> &[Breadcrumb] {
+        unsafe { ErrorImpl::breadcrumbs(self.inner.as_ref()) }
+    }
+}
+
+impl Breadcrumb {
+    /// The message of the breadcrumb.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The location of the [`breadcrumb!`][crate::breadcrumb!] call that
+    /// recorded it.
+    pub fn location(&self) -> &'static Location {
+        self.location
+    }
+}
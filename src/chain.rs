@@ -1,5 +1,8 @@
 use self::ChainState::*;
-use crate::StdError;
+use crate::error::ErrorImpl;
+use crate::location::Location;
+use crate::ptr::RefPtr;
+use crate::{StdError, TypedChain};
 
 use std::vec;
 
@@ -22,6 +25,14 @@ impl<'a> Chain<'a> {
             state: ChainState::Linked { next: Some(head) },
         }
     }
+
+    pub(crate) fn buffered(links: Vec<&'a (dyn StdError + 'static)>) -> Self {
+        Chain {
+            state: ChainState::Buffered {
+                rest: links.into_iter(),
+            },
+        }
+    }
 }
 
 impl<'a> Iterator for Chain<'a> {
@@ -88,3 +99,87 @@ impl Default for Chain<'_> {
         }
     }
 }
+
+// A link of the chain, together with the type name of the value behind it if
+// wallee knows it, and the location of the wallee layer that holds it.
+#[derive(Clone)]
+pub(crate) struct Link<'a> {
+    pub error: &'a (dyn StdError + 'static),
+    pub type_name: Option<&'static str>,
+    pub location: &'static Location,
+}
+
+// The same links as `error.chain()`, found by walking the wallee layers. A
+// layer built on another wallee::Error contributes its context, if any, and
+// the layer where the error originated contributes its context, its error and
+// the source() chain of that error, whose types are unknown. The HttpStatus
+// and ProblemExtension attachments are left out.
+pub(crate) unsafe fn links(this: RefPtr<ErrorImpl>) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut layer = this;
+    loop {
+        let names = unsafe { ErrorImpl::type_names(layer) };
+        let location = unsafe { ErrorImpl::location(layer) };
+        let object = unsafe { ErrorImpl::error(layer) };
+        let attachment = unsafe { ErrorImpl::is_attachment(layer) };
+
+        if let Some(inner) = unsafe { ErrorImpl::inner(layer) } {
+            if let Some(context) = names.context {
+                if !attachment {
+                    links.push(Link {
+                        error: object,
+                        type_name: Some(context),
+                        location,
+                    });
+                }
+            }
+            layer = inner.inner.as_ref();
+            continue;
+        }
+
+        let mut type_names = names.context.into_iter().chain(names.error);
+        let mut errors = Chain::new(object);
+        if attachment {
+            type_names.next();
+            errors.next();
+        }
+        for error in errors {
+            links.push(Link {
+                error,
+                type_name: type_names.next(),
+                location,
+            });
+        }
+        return links;
+    }
+}
+
+impl<'a> TypedChain<'a> {
+    pub(crate) unsafe fn new(this: RefPtr<'a, ErrorImpl>) -> Self {
+        TypedChain {
+            links: unsafe { links(this) }.into_iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for TypedChain<'a> {
+    type Item = (Option<&'static str>, &'a (dyn StdError + 'static));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let link = self.links.next()?;
+        Some((link.type_name, link.error))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.links.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for TypedChain<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let link = self.links.next_back()?;
+        Some((link.type_name, link.error))
+    }
+}
+
+impl ExactSizeIterator for TypedChain<'_> {}
@@ -1,12 +1,12 @@
 use crate::error::ContextError;
-use crate::{Context, Error, StdError};
+use crate::{Context, Error, Located, StdError};
 use core::convert::Infallible;
 use core::fmt::{self, Debug, Display, Write};
 
 #[cfg(error_generic_member_access)]
 use std::error::Request;
 
-mod ext {
+pub(crate) mod ext {
     use super::*;
 
     pub trait StdError {
@@ -37,6 +37,18 @@ mod ext {
             self.context(context)
         }
     }
+
+    impl<E> StdError for Located<E>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        fn ext_context<C>(self, context: C) -> Error
+        where
+            C: Display + Send + Sync + 'static,
+        {
+            Error::from(self).context(context)
+        }
+    }
 }
 
 impl<T, E> Context<T, E> for Result<T, E>
@@ -1,17 +1,27 @@
 use crate::backtrace::Backtrace;
-use crate::chain::Chain;
+use crate::chain::{self, Chain};
+use crate::find;
 use crate::location::Location;
+use crate::origin::{self, Capture, Origin};
 use crate::ptr::MutPtr;
 use crate::ptr::{OwnPtr, RefPtr};
-use crate::{Error, StdError};
+use crate::shared;
+#[cfg(feature = "eyre")]
+use crate::EyreReport;
+use crate::{
+    Breadcrumb, Error, ErrorId, Found, HasLocation, ScopeFrame, SharedError, StdError, Suppressed,
+    TypedChain,
+};
 use alloc::boxed::Box;
-use core::any::TypeId;
+use core::any::{self, TypeId};
 use core::fmt::{self, Debug, Display};
-use core::mem::ManuallyDrop;
+use core::mem::{self, ManuallyDrop};
 use core::ptr;
 use core::ptr::NonNull;
 #[cfg(error_generic_member_access)]
 use std::error::{self, Request};
+use std::thread::Thread;
+use std::time::SystemTime;
 
 use core::ops::{Deref, DerefMut};
 
@@ -34,6 +44,24 @@ impl Error {
         Error::from_std(error, backtrace)
     }
 
+    /// Create a new error object from an error type that records its own
+    /// location, such as one that uses [`derive(Locate)`][crate::Locate].
+    ///
+    /// The `Error` takes on the location stored in the error, rather than the
+    /// location of this call as [`Error::new`] and the `?` operator do.
+    /// [`wallee!`][crate::wallee] picks this conversion on its own for the
+    /// types that derive `Locate`.
+    #[cold]
+    #[must_use]
+    pub fn from_located<E>(error: E) -> Self
+    where
+        E: StdError + HasLocation + Send + Sync + 'static,
+    {
+        let location = error.location();
+        let backtrace = backtrace_if_absent!(&error);
+        Error::from_std(error, backtrace).at(location)
+    }
+
     /// Create a new error object from a printable error message.
     ///
     /// If the argument implements std::error::Error, prefer `Error::new`
@@ -81,25 +109,97 @@ impl Error {
         Error::from_debug(message, backtrace!())
     }
 
+    /// Create a new error object from an error that is not `Send` or `Sync`,
+    /// such as a `Box<dyn Error>` or an error holding an `Rc`.
+    ///
+    /// Since `Error` needs to be threadsafe, the error is not kept. Instead,
+    /// its `source()` chain is walked once and the message and `Debug`
+    /// representation of each error are copied, so that they show up in the
+    /// chain of this `Error` like those of the original errors, which are
+    /// then dropped. The [type name][Error::type_name] is that of `E`, or
+    /// `None` if `E` is a `Box` whose contents are unknown, as are the types of
+    /// the sources. Downcasting to the original types is not possible.
+    ///
+    /// ```
+    /// use std::error::Error as StdError;
+    /// use wallee::Error;
+    ///
+    /// fn legacy() -> Result<(), Box<dyn StdError>> {
+    ///     Err("connection refused".into())
+    /// }
+    ///
+    /// let error = Error::from_local(legacy().unwrap_err()).context("failed to sync");
+    /// assert_eq!(format!("{:#}", error), "failed to sync: connection refused");
+    /// ```
+    #[cold]
+    #[must_use]
+    #[track_caller]
+    pub fn from_local<'a, E>(error: E) -> Self
+    where
+        E: Into<Box<dyn StdError + 'a>>,
+    {
+        use crate::wrapper::LocalError;
+        let error: Box<dyn StdError + 'a> = error.into();
+        let error = LocalError::new(&*error);
+        let vtable = &ErrorVTable {
+            object_drop: object_drop::<LocalError>,
+            object_ref: object_ref::<LocalError>,
+            object_mut: object_mut::<LocalError>,
+            // object_super: object_super::<LocalError>,
+            object_boxed: object_boxed::<LocalError>,
+            object_unbox: object_unbox::<LocalError>,
+            object_downcast: object_downcast::<LocalError>,
+            object_downcast_mut: object_downcast::<LocalError>,
+            object_drop_rest: object_drop_front::<LocalError>,
+            object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: local_type_names::<E>,
+        };
+
+        // Safety: passing vtable that operates on the right type LocalError.
+        unsafe { Error::construct(error, vtable, backtrace!()) }
+    }
+
     #[cold]
     #[track_caller]
     pub(crate) fn from_std<E>(error: E, backtrace: Option<Backtrace>) -> Self
     where
         E: StdError + Send + Sync + 'static,
     {
+        Error::from_std_with(error, backtrace, Capture::Here)
+    }
+
+    #[cold]
+    #[track_caller]
+    pub(crate) fn from_std_with<E>(error: E, backtrace: Option<Backtrace>, capture: Capture) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        // A SharedError already knows the location where it originated.
+        let error = match shared::try_unshare(error) {
+            Ok(shared) => return shared.into_error(),
+            Err(error) => error,
+        };
+
         let vtable = &ErrorVTable {
             object_drop: object_drop::<E>,
             object_ref: object_ref::<E>,
             object_mut: object_mut::<E>,
             // object_super: object_super::<E>,
             object_boxed: object_boxed::<E>,
+            object_unbox: object_unbox::<E>,
             object_downcast: object_downcast::<E>,
+            object_downcast_mut: object_downcast::<E>,
             object_drop_rest: object_drop_front::<E>,
             object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<E>,
         };
 
         // Safety: passing vtable that operates on the right type E.
-        unsafe { Error::construct(error, vtable, backtrace) }
+        unsafe { Error::construct_with(error, vtable, backtrace, capture) }
     }
 
     #[cold]
@@ -116,9 +216,14 @@ impl Error {
             object_mut: object_mut::<MessageError<M>>,
             // object_super: object_super::<MessageError<M>>,
             object_boxed: object_boxed::<MessageError<M>>,
+            object_unbox: object_unbox::<MessageError<M>>,
             object_downcast: object_downcast::<M>,
+            object_downcast_mut: object_downcast::<M>,
             object_drop_rest: object_drop_front::<M>,
             object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<M>,
         };
 
         // Safety: MessageError is repr(transparent) so it is okay for the
@@ -140,9 +245,14 @@ impl Error {
             object_mut: object_mut::<DisplayError<M>>,
             // object_super: object_super::<DisplayError<M>>,
             object_boxed: object_boxed::<DisplayError<M>>,
+            object_unbox: object_unbox::<DisplayError<M>>,
             object_downcast: object_downcast::<M>,
+            object_downcast_mut: object_downcast::<M>,
             object_drop_rest: object_drop_front::<M>,
             object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<M>,
         };
 
         // Safety: DisplayError is repr(transparent) so it is okay for the
@@ -157,6 +267,11 @@ impl Error {
         C: Display + Send + Sync + 'static,
         E: StdError + Send + Sync + 'static,
     {
+        let error = match shared::try_unshare(error) {
+            Ok(shared) => return shared.into_error().context(context),
+            Err(error) => error,
+        };
+
         let error: ContextError<C, E> = ContextError { context, error };
 
         let vtable = &ErrorVTable {
@@ -165,9 +280,14 @@ impl Error {
             object_mut: object_mut::<ContextError<C, E>>,
             // object_super: object_super::<ContextError<C, E>>,
             object_boxed: object_boxed::<ContextError<C, E>>,
+            object_unbox: object_unbox::<ContextError<C, E>>,
             object_downcast: context_downcast::<C, E>,
+            object_downcast_mut: context_downcast::<C, E>,
             object_drop_rest: context_drop_rest::<C, E>,
             object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: context_pop::<C, E>,
+            object_type_names: context_type_names::<C, E>,
         };
 
         // Safety: passing vtable that operates on the right type.
@@ -179,6 +299,16 @@ impl Error {
     pub(crate) fn from_boxed(
         error: Box<dyn StdError + Send + Sync>,
         backtrace: Option<Backtrace>,
+    ) -> Self {
+        Error::from_boxed_with(error, backtrace, Capture::Here)
+    }
+
+    #[cold]
+    #[track_caller]
+    pub(crate) fn from_boxed_with(
+        error: Box<dyn StdError + Send + Sync>,
+        backtrace: Option<Backtrace>,
+        capture: Capture,
     ) -> Self {
         use crate::wrapper::BoxedError;
         let error = BoxedError(error);
@@ -188,14 +318,45 @@ impl Error {
             object_mut: object_mut::<BoxedError>,
             // object_super: object_super::<BoxedError>,
             object_boxed: object_boxed::<BoxedError>,
+            object_unbox: boxed_unbox,
             object_downcast: object_downcast::<Box<dyn StdError + Send + Sync>>,
+            object_downcast_mut: object_downcast::<Box<dyn StdError + Send + Sync>>,
             object_drop_rest: object_drop_front::<Box<dyn StdError + Send + Sync>>,
             object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<Box<dyn StdError + Send + Sync>>,
         };
 
         // Safety: BoxedError is repr(transparent) so it is okay for the vtable
         // to allow casting to Box<dyn StdError + Send + Sync>.
-        unsafe { Error::construct(error, vtable, backtrace) }
+        unsafe { Error::construct_with(error, vtable, backtrace, capture) }
+    }
+
+    #[cold]
+    pub(crate) fn from_shared(error: SharedError) -> Self {
+        let vtable = &ErrorVTable {
+            object_drop: object_drop::<SharedError>,
+            object_ref: shared_ref,
+            object_mut: object_mut::<SharedError>,
+            // object_super: object_super::<SharedError>,
+            object_boxed: object_boxed::<SharedError>,
+            object_unbox: object_unbox::<SharedError>,
+            object_downcast: shared_downcast,
+            object_downcast_mut: object_downcast::<SharedError>,
+            object_drop_rest: object_drop_front::<SharedError>,
+            object_backtrace: shared_backtrace,
+            object_inner: shared_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<SharedError>,
+        };
+
+        // The shared error already has a backtrace.
+        let backtrace = None;
+        let location = error.location();
+
+        // Safety: passing vtable that operates on the right type.
+        unsafe { Error::construct(error, vtable, backtrace) }.at(location)
     }
 
     // Takes backtrace as argument rather than capturing it here so that the
@@ -210,6 +371,22 @@ impl Error {
         vtable: &'static ErrorVTable,
         backtrace: Option<Backtrace>,
     ) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        unsafe { Error::construct_with(error, vtable, backtrace, Capture::Here) }
+    }
+
+    // Errors rebuilt from the parts of another error take over its origin
+    // data instead of capturing it where they are rebuilt.
+    #[cold]
+    #[track_caller]
+    unsafe fn construct_with<E>(
+        error: E,
+        vtable: &'static ErrorVTable,
+        backtrace: Option<Backtrace>,
+        capture: Capture,
+    ) -> Self
     where
         E: StdError + Send + Sync + 'static,
     {
@@ -219,6 +396,7 @@ impl Error {
             vtable,
             backtrace,
             location,
+            origin: None,
             _object: error,
         });
         // Erase the concrete type of E from the compile-time type system. This
@@ -227,8 +405,53 @@ impl Error {
         // result is a thin pointer. The necessary behavior for manipulating the
         // underlying ErrorImpl<E> is preserved in the vtable provided by the
         // caller rather than a builtin fat pointer vtable.
-        let inner = OwnPtr::new(inner).cast::<ErrorImpl>();
-        Error { inner }
+        let mut inner = OwnPtr::new(inner).cast::<ErrorImpl>();
+        match capture {
+            Capture::Here => unsafe { ErrorImpl::capture_origin(inner) },
+            Capture::Given(origin) => unsafe { inner.deref_mut() }.origin = origin,
+        }
+        Error { inner }.enter_scopes()
+    }
+
+    // Attaches the scopes that were active where the error originated as
+    // context, innermost first, each with the location of its scope.
+    fn enter_scopes(mut self) -> Self {
+        let origin = match unsafe { self.inner.deref_mut() }.origin.as_mut() {
+            Some(origin) if !origin.scopes.is_empty() => origin,
+            _ => return self,
+        };
+        let scopes = mem::take(&mut origin.scopes);
+        if origin.is_empty() {
+            unsafe { self.inner.deref_mut() }.origin = None;
+        }
+        let mut error = self;
+        for frame in scopes.into_iter().rev() {
+            let location = frame.location();
+            error = error.context(frame);
+            unsafe { *ErrorImpl::location_mut(error.inner.as_mut()) = location };
+        }
+        error
+    }
+
+    // Replaces the location recorded when this error was constructed, for
+    // errors that are rebuilt from parts of another error. The scopes that
+    // were attached as the error was constructed keep their own locations.
+    pub(crate) fn at(self, location: &'static Location) -> Self {
+        let mut layer = self.inner;
+        while unsafe { ErrorImpl::is_scope(layer.as_ref()) } {
+            layer = match unsafe { ErrorImpl::inner(layer.as_ref()) } {
+                Some(inner) => inner.inner,
+                None => break,
+            };
+        }
+        unsafe { *ErrorImpl::location_mut(layer.as_mut()) = location };
+        self
+    }
+
+    fn extend_suppressed(&mut self, suppressed: Vec<Error>) {
+        if !suppressed.is_empty() {
+            unsafe { ErrorImpl::suppressed_mut(self.inner.as_mut()) }.extend(suppressed);
+        }
     }
 
     /// Wrap the error value with additional context.
@@ -303,9 +526,14 @@ impl Error {
             object_mut: object_mut::<ContextError<C, Error>>,
             // object_super: object_super::<ContextError<C, Error>>,
             object_boxed: object_boxed::<ContextError<C, Error>>,
+            object_unbox: object_unbox::<ContextError<C, Error>>,
             object_downcast: context_chain_downcast::<C>,
+            object_downcast_mut: context_chain_downcast_mut::<C>,
             object_drop_rest: context_chain_drop_rest::<C>,
             object_backtrace: context_backtrace::<C>,
+            object_inner: context_inner::<C>,
+            object_pop_context: context_chain_pop::<C>,
+            object_type_names: context_chain_type_names::<C>,
         };
 
         // As the cause is wallee::Error, we already have a backtrace for it.
@@ -315,6 +543,333 @@ impl Error {
         unsafe { Error::construct(error, vtable, backtrace) }
     }
 
+    /// Convert an [`anyhow::Error`] into a wallee `Error`.
+    ///
+    /// The anyhow error's cause chain and backtrace are kept, and the location
+    /// of the call to this function is recorded as the error's origin. Errors
+    /// that had been converted from wallee into anyhow in the first place get
+    /// their original location back.
+    ///
+    /// A `From` impl is not possible here, as it would overlap with the
+    /// conversion from any `std::error::Error`, so use this function or
+    /// `wallee!(error)` where `?` would otherwise be used.
+    ///
+    /// ```
+    /// use wallee::{Error, Result};
+    ///
+    /// fn third_party() -> anyhow::Result<u32> {
+    ///     anyhow::bail!("rate limited")
+    /// }
+    ///
+    /// fn fetch() -> Result<u32> {
+    ///     third_party().map_err(Error::from_anyhow)
+    /// }
+    /// #
+    /// # assert_eq!(fetch().unwrap_err().to_string(), "rate limited");
+    /// ```
+    #[cfg(feature = "anyhow")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "anyhow")))]
+    #[cold]
+    #[must_use]
+    #[track_caller]
+    pub fn from_anyhow(error: anyhow::Error) -> Self {
+        use crate::wrapper::AnyhowError;
+
+        // An error that was converted from wallee in the first place.
+        let error = if (*error).is::<SharedError>() {
+            match error.downcast::<SharedError>() {
+                Ok(shared) => return shared.into_error(),
+                Err(error) => error,
+            }
+        } else {
+            error
+        };
+
+        let error = AnyhowError(error);
+        let vtable = &ErrorVTable {
+            object_drop: object_drop::<AnyhowError>,
+            object_ref: object_ref::<AnyhowError>,
+            object_mut: object_mut::<AnyhowError>,
+            // object_super: object_super::<AnyhowError>,
+            object_boxed: object_boxed::<AnyhowError>,
+            object_unbox: object_unbox::<AnyhowError>,
+            object_downcast: object_downcast::<anyhow::Error>,
+            object_downcast_mut: object_downcast::<anyhow::Error>,
+            object_drop_rest: object_drop_front::<anyhow::Error>,
+            object_backtrace: anyhow_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<anyhow::Error>,
+        };
+
+        // The anyhow::Error already has a backtrace.
+        let backtrace = None;
+
+        // Safety: AnyhowError is repr(transparent) so it is okay for the
+        // vtable to allow casting to anyhow::Error.
+        unsafe { Error::construct(error, vtable, backtrace) }
+    }
+
+    /// Convert an [`eyre::Report`] into a wallee `Error`.
+    ///
+    /// The report's cause chain is kept, and the location of the call to this
+    /// function is recorded as the error's origin. The report itself, with its
+    /// handler and any sections attached to it, is kept as well and comes back
+    /// out unchanged when converting this error back into an `eyre::Report`.
+    /// Reports that had been converted from wallee in the first place get
+    /// their original location back.
+    ///
+    /// As with [`Error::from_anyhow`], a `From` impl is not possible, so use
+    /// this function or `wallee!(report)` where `?` would otherwise be used.
+    ///
+    /// ```
+    /// use wallee::{Error, Result};
+    ///
+    /// fn third_party() -> eyre::Result<u32> {
+    ///     eyre::bail!("rate limited")
+    /// }
+    ///
+    /// fn fetch() -> Result<u32> {
+    ///     third_party().map_err(Error::from_eyre)
+    /// }
+    /// #
+    /// # assert_eq!(fetch().unwrap_err().to_string(), "rate limited");
+    /// ```
+    #[cfg(feature = "eyre")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "eyre")))]
+    #[cold]
+    #[must_use]
+    #[track_caller]
+    pub fn from_eyre(report: eyre::Report) -> Self {
+        // A report that was converted from wallee in the first place.
+        let report = if (*report).is::<SharedError>() {
+            match report.downcast::<SharedError>() {
+                Ok(shared) => return shared.into_error(),
+                Err(report) => report,
+            }
+        } else {
+            report
+        };
+
+        use crate::wrapper::EyreError;
+        // The backtrace of an eyre::Report, if any, is held by its handler
+        // where it cannot be reached.
+        let backtrace = backtrace_if_absent!(&*report);
+        let error = EyreError(report);
+        let vtable = &ErrorVTable {
+            object_drop: object_drop::<EyreError>,
+            object_ref: object_ref::<EyreError>,
+            object_mut: object_mut::<EyreError>,
+            // object_super: object_super::<EyreError>,
+            object_boxed: object_boxed::<EyreError>,
+            object_unbox: object_unbox::<EyreError>,
+            object_downcast: object_downcast::<eyre::Report>,
+            object_downcast_mut: object_downcast::<eyre::Report>,
+            object_drop_rest: object_drop_front::<eyre::Report>,
+            object_backtrace: no_backtrace,
+            object_inner: no_inner,
+            object_pop_context: no_context,
+            object_type_names: error_type_names::<eyre::Report>,
+        };
+
+        // Safety: EyreError is repr(transparent) so it is okay for the vtable
+        // to allow casting to eyre::Report.
+        unsafe { Error::construct(error, vtable, backtrace) }
+    }
+
+    /// Render this error with the installed eyre report handler, such as the
+    /// one set up by `color_eyre::install()`.
+    ///
+    /// The handler is given the error's cause chain and the location where the
+    /// error originated, so that binaries mixing wallee and eyre print all of
+    /// their errors in the same format.
+    ///
+    /// ```
+    /// use wallee::wallee;
+    ///
+    /// let error = wallee!("disk full").context("failed to save");
+    /// eprintln!("Error: {:?}", error.eyre_report());
+    /// ```
+    #[cfg(feature = "eyre")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "eyre")))]
+    pub fn eyre_report(&self) -> EyreReport<'_> {
+        EyreReport::new(self)
+    }
+
+    /// Convert this error into a [`SharedError`], which can be cloned
+    /// cheaply to hand the same failure to many recipients.
+    pub fn share(self) -> SharedError {
+        SharedError::from(self)
+    }
+
+    /// Remove the outermost context from this error.
+    ///
+    /// On success, returns the context together with the error it had been
+    /// attached to. The remaining error keeps its own location, or the
+    /// location of the context if the context was attached to a plain
+    /// `std::error::Error` through the [`Context`][crate::Context] trait.
+    /// Errors [suppressed][Error::suppressed] on the removed layer are moved
+    /// onto the remaining error.
+    ///
+    /// If this error does not have any context, it is returned unchanged in the
+    /// `Err` variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, Context, Result};
+    ///
+    /// fn connect() -> Result<()> {
+    ///     Err(wallee!("connection refused")).context("failed to connect to db.internal:5432")
+    /// }
+    ///
+    /// let error = connect().unwrap_err();
+    /// let (context, error) = error.pop_context().unwrap();
+    /// assert_eq!(context.to_string(), "failed to connect to db.internal:5432");
+    /// assert_eq!(error.to_string(), "connection refused");
+    /// assert!(error.pop_context().is_err());
+    /// ```
+    pub fn pop_context(self) -> Result<(Box<dyn Display + Send + Sync>, Self), Self> {
+        let outer = ManuallyDrop::new(self);
+        match unsafe { (vtable(outer.inner.ptr).object_pop_context)(outer.inner) } {
+            Some(parts) => Ok(parts),
+            None => Err(ManuallyDrop::into_inner(outer)),
+        }
+    }
+
+    /// Replace the outermost context of this error.
+    ///
+    /// The new context keeps the location of the context it replaces. If this
+    /// error does not have any context, it is returned unchanged and `f` is not
+    /// called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, Context, Result};
+    ///
+    /// fn connect() -> Result<()> {
+    ///     Err(wallee!("connection refused")).context("failed to connect to db.internal:5432")
+    /// }
+    ///
+    /// let error = connect()
+    ///     .unwrap_err()
+    ///     .map_context(|_internal| "the database is unavailable");
+    /// assert_eq!(format!("{:#}", error), "the database is unavailable: connection refused");
+    /// ```
+    #[must_use]
+    pub fn map_context<C, F>(self, f: F) -> Self
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce(Box<dyn Display + Send + Sync>) -> C,
+    {
+        let location = self.location();
+        match self.pop_context() {
+            Ok((context, error)) => error.context(f(context)).at(location),
+            Err(error) => error,
+        }
+    }
+
+    /// Remove all context from this error, leaving the error it was originally
+    /// constructed from along with that error's location.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, Context, Result};
+    ///
+    /// fn attempt() -> Result<()> {
+    ///     Err(wallee!("timed out"))
+    ///         .context("failed to fetch page 2")
+    ///         .context("failed to sync")
+    /// }
+    ///
+    /// let error = attempt().unwrap_err().into_root();
+    /// assert_eq!(format!("{:#}", error), "timed out");
+    /// ```
+    #[must_use]
+    pub fn into_root(self) -> Self {
+        let mut error = self;
+        loop {
+            match error.pop_context() {
+                Ok((_context, inner)) => error = inner,
+                Err(root) => return root,
+            }
+        }
+    }
+
+    /// Take this error apart into its underlying error object, the location
+    /// where it originated, and its backtrace.
+    ///
+    /// The error object is the outermost one: for an error with
+    /// [context][crate::Context], it displays the context and its
+    /// [`source()`][std::error::Error::source] chain leads through the error
+    /// the context was attached to. The backtrace is `None` if none was
+    /// captured, if the error object carries its own backtrace, or if the
+    /// context was attached to another `wallee::Error`, which keeps its
+    /// backtrace inside the error object.
+    ///
+    /// The rest is dropped: the ID, thread, time, scopes, breadcrumbs and
+    /// [suppressed][Error::suppressed] errors. When the context was attached
+    /// to another `wallee::Error`, that error and what it recorded stay inside
+    /// the error object, but only its messages remain reachable, through
+    /// `source()`. After [`Error::from_parts`], the context is no longer a
+    /// layer of its own, so it cannot be [downcast][Error::downcast_ref] to or
+    /// [popped][Error::pop_context], and every error of the chain reports the
+    /// given location.
+    ///
+    /// This is for moving an error through some other container, such as an
+    /// FFI handle or a custom serializer. [`Error::from_parts`] puts the
+    /// pieces back together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, Error};
+    ///
+    /// let error = wallee!("connection refused").context("failed to connect");
+    /// let line = error.line();
+    ///
+    /// let (object, location, backtrace) = error.into_parts();
+    /// assert_eq!(object.to_string(), "failed to connect");
+    ///
+    /// let error = Error::from_parts(object, location, backtrace);
+    /// assert_eq!(error.line(), line);
+    /// assert_eq!(format!("{:#}", error), "failed to connect: connection refused");
+    /// ```
+    #[must_use]
+    pub fn into_parts(
+        self,
+    ) -> (
+        Box<dyn StdError + Send + Sync + 'static>,
+        &'static Location,
+        Option<impl_backtrace!()>,
+    ) {
+        let outer = ManuallyDrop::new(self);
+        unsafe { (vtable(outer.inner.ptr).object_unbox)(outer.inner) }
+    }
+
+    /// Rebuild an error from the parts returned by [`Error::into_parts`].
+    ///
+    /// The error reports the given location rather than the caller's. If no
+    /// backtrace is given and the error object does not provide one either, a
+    /// backtrace is captured here, as with [`Error::new`]. Unlike
+    /// [`Error::new`], the error does not record an ID, a thread, a time, the
+    /// active scopes or the breadcrumbs here, since it originated elsewhere.
+    #[cold]
+    #[must_use]
+    pub fn from_parts(
+        error: Box<dyn StdError + Send + Sync + 'static>,
+        location: &'static Location,
+        backtrace: Option<impl_backtrace!()>,
+    ) -> Self {
+        let backtrace = match backtrace {
+            Some(backtrace) => Some(backtrace),
+            None => backtrace_if_absent!(&*error),
+        };
+        Error::from_boxed_with(error, backtrace, Capture::Given(None)).at(location)
+    }
+
     /// Get the backtrace for this Error.
     ///
     /// In order for the backtrace to be meaningful, one of the two environment
@@ -346,6 +901,11 @@ impl Error {
         unsafe { ErrorImpl::backtrace(self.inner.as_ref()) }
     }
 
+    /// Returns the caller location from which the error originated.
+    pub fn location(&self) -> &'static Location {
+        unsafe { ErrorImpl::location(self.inner.as_ref()) }
+    }
+
     /// Returns the name of the source file from which the error originated.
     pub fn file(&self) -> &str {
         unsafe { ErrorImpl::location(self.inner.as_ref()).file() }
@@ -365,7 +925,9 @@ impl Error {
     ///
     /// This iterator will visit every error in the cause chain of this error
     /// object, beginning with the error that this error object was created
-    /// from.
+    /// from. The [`HttpStatus`][crate::HttpStatus] and
+    /// [`ProblemExtension`][crate::ProblemExtension] values attached as
+    /// context are left out.
     ///
     /// # Example
     ///
@@ -383,10 +945,51 @@ impl Error {
     /// }
     /// ```
     #[cold]
-    pub fn chain(&self) -> Chain {
+    pub fn chain(&self) -> Chain<'_> {
         unsafe { ErrorImpl::chain(self.inner.as_ref()) }
     }
 
+    /// An iterator of the chain of source errors contained by this Error,
+    /// along with the name of the concrete type of each one where it is
+    /// known.
+    ///
+    /// See [`TypedChain`] for which type names are known.
+    #[cold]
+    pub fn typed_chain(&self) -> TypedChain<'_> {
+        unsafe { TypedChain::new(self.inner.as_ref()) }
+    }
+
+    /// The name of the concrete type of the outermost error, message or
+    /// context value held by this Error, as returned by
+    /// [`core::any::type_name`].
+    ///
+    /// This is `None` only for an error made by [`Error::from_local`] from a
+    /// boxed error, whose concrete type is not known.
+    ///
+    /// ```
+    /// use std::io;
+    /// use wallee::{Context, Error};
+    ///
+    /// let error = Error::new(io::Error::other("oh no!"));
+    /// assert_eq!(error.type_name(), Some(std::any::type_name::<io::Error>()));
+    ///
+    /// let error = error.context(String::from("context"));
+    /// assert_eq!(error.type_name(), Some("alloc::string::String"));
+    /// ```
+    pub fn type_name(&self) -> Option<&'static str> {
+        let mut layer = self.inner.as_ref();
+        loop {
+            let names = unsafe { ErrorImpl::type_names(layer) };
+            if names.context.is_some() {
+                return names.context;
+            }
+            match unsafe { ErrorImpl::inner(layer) } {
+                Some(inner) => layer = inner.inner.as_ref(),
+                None => return names.error,
+            }
+        }
+    }
+
     /// The lowest level cause of this error &mdash; this error's cause's
     /// cause's cause etc.
     ///
@@ -396,6 +999,52 @@ impl Error {
         self.chain().last().unwrap()
     }
 
+    /// Record a secondary error that occurred while this error was already
+    /// being propagated, such as a failed rollback or cleanup step.
+    ///
+    /// Suppressed errors are not part of the cause chain and do not take part
+    /// in downcasting. They are listed in a "Suppressed:" section of the Debug
+    /// representation, each with its own caller location.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wallee::{wallee, Result};
+    /// #
+    /// # fn write_rows() -> Result<()> {
+    /// #     Err(wallee!("disk full"))
+    /// # }
+    /// #
+    /// # fn rollback() -> Result<()> {
+    /// #     Err(wallee!("connection reset"))
+    /// # }
+    /// #
+    /// fn commit() -> Result<()> {
+    ///     if let Err(mut error) = write_rows() {
+    ///         if let Err(rollback_error) = rollback() {
+    ///             error.add_suppressed(rollback_error);
+    ///         }
+    ///         return Err(error);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// #
+    /// # assert_eq!(commit().unwrap_err().suppressed().count(), 1);
+    /// ```
+    pub fn add_suppressed(&mut self, error: Error) {
+        unsafe { ErrorImpl::suppressed_mut(self.inner.as_mut()) }.push(error);
+    }
+
+    /// An iterator of the errors that were suppressed while this error was
+    /// being propagated.
+    ///
+    /// This includes errors suppressed on any `wallee::Error` underneath
+    /// context that has since been attached, starting from the outermost
+    /// context.
+    pub fn suppressed(&self) -> Suppressed<'_> {
+        Suppressed::new(self.inner.as_ref())
+    }
+
     /// Returns true if `E` is the type held by this error object.
     ///
     /// For errors with context, this method returns true if `E` matches the
@@ -421,7 +1070,7 @@ impl Error {
         unsafe {
             // Use vtable to find NonNull<()> which points to a value of type E
             // somewhere inside the data structure.
-            let addr = match (vtable(inner.ptr).object_downcast)(inner, target) {
+            let addr = match (vtable(inner.ptr).object_downcast_mut)(inner, target) {
                 Some(addr) => addr,
                 None => return Err(self),
             };
@@ -498,11 +1147,95 @@ impl Error {
         unsafe {
             // Use vtable to find NonNull<()> which points to a value of type E
             // somewhere inside the data structure.
-            let addr = (vtable(self.inner.ptr).object_downcast)(self.inner, target)?;
+            let addr = (vtable(self.inner.ptr).object_downcast_mut)(self.inner, target)?;
             Some(addr.cast::<E>().deref_mut())
         }
     }
 
+    /// Search this error for a value of type `E`.
+    ///
+    /// Unlike [`downcast_ref`][Error::downcast_ref], which only looks at the
+    /// error and context types that `wallee` wrapped, this also follows the
+    /// `source()` links of every error in the chain. This finds errors that
+    /// are held by another error type, such as an `io::Error` underneath a
+    /// `thiserror` enum.
+    ///
+    /// The result also reports the location of the nearest `wallee::Error`
+    /// layer that holds the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io;
+    /// use thiserror::Error;
+    /// use wallee::{Context, Result};
+    ///
+    /// #[derive(Error, Debug)]
+    /// enum ConfigError {
+    ///     #[error("failed to read config")]
+    ///     Read(#[source] io::Error),
+    /// }
+    ///
+    /// fn load() -> Result<()> {
+    ///     let io_error = io::Error::new(io::ErrorKind::NotFound, "no such file");
+    ///     Err(ConfigError::Read(io_error)).context("failed to start")
+    /// }
+    ///
+    /// let error = load().unwrap_err();
+    /// assert!(error.downcast_ref::<io::Error>().is_none());
+    ///
+    /// let io_error = error.find::<io::Error>().unwrap();
+    /// assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+    /// assert_eq!(io_error.location().file(), file!());
+    /// ```
+    pub fn find<E>(&self) -> Option<Found<&E>>
+    where
+        E: StdError + 'static,
+    {
+        let mut first = None;
+        find::search(self, |value, location| {
+            first = Some(Found::new(value, location));
+            false
+        });
+        first
+    }
+
+    /// Search this error for a value of type `E` by mutable reference.
+    ///
+    /// Only the error, message and context values held directly by `wallee`
+    /// can be found this way, since the `source()` links of
+    /// `std::error::Error` only give out shared references. Those values need
+    /// not implement `std::error::Error`, as with
+    /// [`downcast_mut`][Error::downcast_mut].
+    pub fn find_mut<E>(&mut self) -> Option<Found<&mut E>>
+    where
+        E: Display + Debug + Send + Sync + 'static,
+    {
+        find::search_mut(self)
+    }
+
+    /// Search this error for every value of type `E`, outermost first.
+    pub fn find_all<E>(&self) -> Vec<Found<&E>>
+    where
+        E: StdError + 'static,
+    {
+        let mut all = Vec::new();
+        find::search(self, |value, location| {
+            all.push(Found::new(value, location));
+            true
+        });
+        all
+    }
+
+    /// Returns true if a value of type `E` can be [found][Error::find]
+    /// anywhere in this error.
+    pub fn contains<E>(&self) -> bool
+    where
+        E: StdError + 'static,
+    {
+        self.find::<E>().is_some()
+    }
+
     #[cfg(error_generic_member_access)]
     pub(crate) fn provide<'a>(&'a self, request: &mut Request<'a>) {
         unsafe { ErrorImpl::provide(self.inner.by_ref(), request) }
@@ -567,15 +1300,69 @@ impl Drop for Error {
     }
 }
 
+// The outermost context of an error, split apart from the error underneath it.
+type ContextParts = (Box<dyn Display + Send + Sync>, Error);
+
+// An error taken apart into its object, location and backtrace.
+type ErrorParts = (
+    Box<dyn StdError + Send + Sync + 'static>,
+    &'static Location,
+    Option<Backtrace>,
+);
+
+// The type names of the context and of the error held by a layer. Context
+// attached to another wallee::Error has that Error as its error.
+#[derive(Copy, Clone)]
+pub(crate) struct TypeNames {
+    pub context: Option<&'static str>,
+    pub error: Option<&'static str>,
+}
+
 struct ErrorVTable {
     object_drop: unsafe fn(OwnPtr<ErrorImpl>),
     object_ref: unsafe fn(RefPtr<ErrorImpl>) -> &(dyn StdError + Send + Sync + 'static),
     object_mut: unsafe fn(MutPtr<ErrorImpl>) -> &mut (dyn StdError + Send + Sync + 'static),
     // object_super: unsafe fn(RefPtr<ErrorImpl>) -> &(dyn StdError + Send + Sync + 'static),
     object_boxed: unsafe fn(OwnPtr<ErrorImpl>) -> Box<dyn StdError + Send + Sync + 'static>,
+    object_unbox: unsafe fn(OwnPtr<ErrorImpl>) -> ErrorParts,
     object_downcast: unsafe fn(OwnPtr<ErrorImpl>, TypeId) -> Option<OwnPtr<()>>,
+    object_downcast_mut: unsafe fn(OwnPtr<ErrorImpl>, TypeId) -> Option<OwnPtr<()>>,
     object_drop_rest: unsafe fn(OwnPtr<ErrorImpl>, TypeId),
     object_backtrace: unsafe fn(RefPtr<ErrorImpl>) -> Option<&Backtrace>,
+    object_inner: unsafe fn(RefPtr<ErrorImpl>) -> Option<&Error>,
+    object_pop_context: unsafe fn(OwnPtr<ErrorImpl>) -> Option<ContextParts>,
+    object_type_names: fn() -> TypeNames,
+}
+
+fn error_type_names<E>() -> TypeNames
+where
+    E: ?Sized,
+{
+    TypeNames {
+        context: None,
+        error: Some(any::type_name::<E>()),
+    }
+}
+
+// The snapshot of a local error keeps the name of the type it was made from,
+// unless that is a box hiding the type of the error inside.
+fn local_type_names<E>() -> TypeNames {
+    let error = any::type_name::<E>();
+    TypeNames {
+        context: None,
+        error: (!error.starts_with("alloc::boxed::Box<")).then_some(error),
+    }
+}
+
+fn context_type_names<C, E>() -> TypeNames {
+    TypeNames {
+        context: Some(any::type_name::<C>()),
+        error: Some(any::type_name::<E>()),
+    }
+}
+
+fn context_chain_type_names<C>() -> TypeNames {
+    context_type_names::<C, Error>()
 }
 
 // Safety: requires layout of *e to match ErrorImpl<E>.
@@ -597,21 +1384,21 @@ unsafe fn object_drop_front<E>(e: OwnPtr<ErrorImpl>, target: TypeId) {
 }
 
 // Safety: requires layout of *e to match ErrorImpl<E>.
-unsafe fn object_ref<E>(e: RefPtr<ErrorImpl>) -> &(dyn StdError + Send + Sync + 'static)
+unsafe fn object_ref<E>(e: RefPtr<'_, ErrorImpl>) -> &(dyn StdError + Send + Sync + 'static)
 where
     E: StdError + Send + Sync + 'static,
 {
     // Attach E's native StdError vtable onto a pointer to self._object.
-    &e.cast::<ErrorImpl<E>>().as_ref()._object
+    unsafe { &e.cast::<ErrorImpl<E>>().as_ref()._object }
 }
 
 // Safety: requires layout of *e to match ErrorImpl<E>.
-unsafe fn object_mut<E>(e: MutPtr<ErrorImpl>) -> &mut (dyn StdError + Send + Sync + 'static)
+unsafe fn object_mut<E>(e: MutPtr<'_, ErrorImpl>) -> &mut (dyn StdError + Send + Sync + 'static)
 where
     E: StdError + Send + Sync + 'static,
 {
     // Attach E's native StdError vtable onto a pointer to self._object.
-    &mut e.cast::<ErrorImpl<E>>().as_mut()._object
+    unsafe { &mut e.cast::<ErrorImpl<E>>().as_mut()._object }
 }
 
 // Safety: requires layout of *e to match ErrorImpl<E>.
@@ -633,6 +1420,28 @@ where
     unsafe { unerased_own.boxed() }
 }
 
+// Safety: requires layout of *e to match ErrorImpl<E>.
+unsafe fn object_unbox<E>(e: OwnPtr<ErrorImpl>) -> ErrorParts
+where
+    E: StdError + Send + Sync + 'static,
+{
+    // Box the bare E, leaving behind the rest of ErrorImpl<E>.
+    let unerased = *unsafe { e.cast::<ErrorImpl<E>>().boxed() };
+    (
+        Box::new(unerased._object),
+        unerased.location,
+        unerased.backtrace,
+    )
+}
+
+// Safety: requires layout of *e to match ErrorImpl<BoxedError>.
+unsafe fn boxed_unbox(e: OwnPtr<ErrorImpl>) -> ErrorParts {
+    use crate::wrapper::BoxedError;
+    // Hand back the original box rather than boxing it a second time.
+    let unerased = *unsafe { e.cast::<ErrorImpl<BoxedError>>().boxed() };
+    (unerased._object.0, unerased.location, unerased.backtrace)
+}
+
 // Safety: requires layout of *e to match ErrorImpl<E>.
 unsafe fn object_downcast<E>(e: OwnPtr<ErrorImpl>, target: TypeId) -> Option<OwnPtr<()>>
 where
@@ -654,7 +1463,7 @@ where
     }
 }
 
-fn no_backtrace(e: RefPtr<ErrorImpl>) -> Option<&Backtrace> {
+fn no_backtrace(e: RefPtr<'_, ErrorImpl>) -> Option<&Backtrace> {
     let _ = e;
     None
 }
@@ -667,11 +1476,11 @@ where
 {
     if TypeId::of::<C>() == target {
         let unerased_ref = e.cast::<ErrorImpl<ContextError<C, E>>>();
-        let unerased = unerased_ref.deref();
+        let unerased = unsafe { unerased_ref.deref() };
         Some(OwnPtr::from_raw(NonNull::from(&unerased._object.context)).cast::<()>())
     } else if TypeId::of::<E>() == target {
         let unerased_ref = e.cast::<ErrorImpl<ContextError<C, E>>>();
-        let unerased = unerased_ref.deref();
+        let unerased = unsafe { unerased_ref.deref() };
         Some(OwnPtr::from_raw(NonNull::from(&unerased._object.error)).cast::<()>())
     } else {
         None
@@ -701,7 +1510,7 @@ where
     C: 'static,
 {
     let unerased_ref = e.cast::<ErrorImpl<ContextError<C, Error>>>();
-    let unerased = unerased_ref.deref();
+    let unerased = unsafe { unerased_ref.deref() };
     if TypeId::of::<C>() == target {
         Some(OwnPtr::from_raw(NonNull::from(&unerased._object.context)).cast::<()>())
     } else {
@@ -711,6 +1520,22 @@ where
     }
 }
 
+// Safety: requires layout of *e to match ErrorImpl<ContextError<C, Error>>.
+unsafe fn context_chain_downcast_mut<C>(e: OwnPtr<ErrorImpl>, target: TypeId) -> Option<OwnPtr<()>>
+where
+    C: 'static,
+{
+    let unerased_ref = e.cast::<ErrorImpl<ContextError<C, Error>>>();
+    let unerased = unsafe { unerased_ref.deref() };
+    if TypeId::of::<C>() == target {
+        Some(OwnPtr::from_raw(NonNull::from(&unerased._object.context)).cast::<()>())
+    } else {
+        // Recurse down the context chain, stopping at a shared error.
+        let source = &unerased._object.error;
+        unsafe { (vtable(source.inner.ptr).object_downcast_mut)(source.inner, target) }
+    }
+}
+
 // Safety: requires layout of *e to match ErrorImpl<ContextError<C, Error>>.
 unsafe fn context_chain_drop_rest<C>(e: OwnPtr<ErrorImpl>, target: TypeId)
 where
@@ -736,15 +1561,108 @@ where
 
 // Safety: requires layout of *e to match ErrorImpl<ContextError<C, Error>>.
 #[allow(clippy::unnecessary_wraps)]
-unsafe fn context_backtrace<C>(e: RefPtr<ErrorImpl>) -> Option<&Backtrace>
+unsafe fn context_backtrace<C>(e: RefPtr<'_, ErrorImpl>) -> Option<&Backtrace>
 where
     C: 'static,
 {
-    let unerased = e.cast::<ErrorImpl<ContextError<C, Error>>>().as_ref();
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<C, Error>>>().as_ref() };
     let backtrace = unsafe { ErrorImpl::backtrace(unerased._object.error.inner.as_ref()) };
     Some(backtrace)
 }
 
+fn no_inner(e: RefPtr<'_, ErrorImpl>) -> Option<&Error> {
+    let _ = e;
+    None
+}
+
+// Safety: requires layout of *e to match ErrorImpl<ContextError<C, Error>>.
+unsafe fn context_inner<C>(e: RefPtr<'_, ErrorImpl>) -> Option<&Error>
+where
+    C: 'static,
+{
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<C, Error>>>().as_ref() };
+    Some(&unerased._object.error)
+}
+
+// Safety: requires layout of *e to match ErrorImpl<AnyhowError>.
+#[cfg(feature = "anyhow")]
+#[allow(clippy::unnecessary_wraps)]
+unsafe fn anyhow_backtrace(e: RefPtr<'_, ErrorImpl>) -> Option<&Backtrace> {
+    use crate::wrapper::AnyhowError;
+    let unerased = unsafe { e.cast::<ErrorImpl<AnyhowError>>().as_ref() };
+    Some(unerased._object.0.backtrace())
+}
+
+// Safety: requires layout of *e to match ErrorImpl<SharedError>.
+unsafe fn shared_ref(e: RefPtr<'_, ErrorImpl>) -> &(dyn StdError + Send + Sync + 'static) {
+    let unerased = unsafe { e.cast::<ErrorImpl<SharedError>>().as_ref() };
+    unsafe { ErrorImpl::error(unerased._object.inner.inner.as_ref()) }
+}
+
+// Safety: requires layout of *e to match ErrorImpl<SharedError>.
+unsafe fn shared_downcast(e: OwnPtr<ErrorImpl>, target: TypeId) -> Option<OwnPtr<()>> {
+    let unerased_ref = e.cast::<ErrorImpl<SharedError>>();
+    let unerased = unsafe { unerased_ref.deref() };
+    if TypeId::of::<SharedError>() == target {
+        Some(OwnPtr::from_raw(NonNull::from(&unerased._object)).cast::<()>())
+    } else {
+        // The values of the shared error can be handed out by reference only,
+        // as the other clones hold on to them too.
+        let source = &unerased._object.inner;
+        unsafe { (vtable(source.inner.ptr).object_downcast)(source.inner, target) }
+    }
+}
+
+// Safety: requires layout of *e to match ErrorImpl<SharedError>.
+#[allow(clippy::unnecessary_wraps)]
+unsafe fn shared_backtrace(e: RefPtr<'_, ErrorImpl>) -> Option<&Backtrace> {
+    let unerased = unsafe { e.cast::<ErrorImpl<SharedError>>().as_ref() };
+    Some(unerased._object.backtrace())
+}
+
+// Safety: requires layout of *e to match ErrorImpl<SharedError>.
+#[allow(clippy::unnecessary_wraps)]
+unsafe fn shared_inner(e: RefPtr<'_, ErrorImpl>) -> Option<&Error> {
+    let unerased = unsafe { e.cast::<ErrorImpl<SharedError>>().as_ref() };
+    Some(&unerased._object)
+}
+
+fn no_context(e: OwnPtr<ErrorImpl>) -> Option<ContextParts> {
+    let _ = e;
+    None
+}
+
+// Safety: requires layout of *e to match ErrorImpl<ContextError<C, E>>.
+unsafe fn context_pop<C, E>(e: OwnPtr<ErrorImpl>) -> Option<ContextParts>
+where
+    C: Display + Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+{
+    // Split the context from the error it was attached to. The error had no
+    // location of its own before the context was attached, so it takes over
+    // the location and backtrace of the context.
+    let unerased_own = e.cast::<ErrorImpl<ContextError<C, E>>>();
+    let unerased = *unsafe { unerased_own.boxed() };
+    let ContextError { context, error } = unerased._object;
+    let capture = Capture::Given(unerased.origin);
+    let error = Error::from_std_with(error, unerased.backtrace, capture).at(unerased.location);
+    Some((Box::new(context), error))
+}
+
+// Safety: requires layout of *e to match ErrorImpl<ContextError<C, Error>>.
+unsafe fn context_chain_pop<C>(e: OwnPtr<ErrorImpl>) -> Option<ContextParts>
+where
+    C: Display + Send + Sync + 'static,
+{
+    let unerased_own = e.cast::<ErrorImpl<ContextError<C, Error>>>();
+    let unerased = *unsafe { unerased_own.boxed() };
+    let ContextError { context, mut error } = unerased._object;
+    if let Some(origin) = unerased.origin {
+        error.extend_suppressed(origin.suppressed);
+    }
+    Some((Box::new(context), error))
+}
+
 // NOTE: If working with `ErrorImpl<()>`, references should be avoided in favor
 // of raw pointers and `NonNull`.
 // repr C to ensure that E remains in the final position.
@@ -752,7 +1670,8 @@ where
 pub(crate) struct ErrorImpl<E = ()> {
     vtable: &'static ErrorVTable,
     backtrace: Option<Backtrace>,
-    location: Location,
+    location: &'static Location,
+    origin: Option<Box<Origin>>,
     // NOTE: Don't use directly. Use only through vtable. Erased type may have
     // different alignment.
     _object: E,
@@ -774,7 +1693,7 @@ pub(crate) struct ContextError<C, E> {
 }
 
 impl<E> ErrorImpl<E> {
-    fn erase(&self) -> RefPtr<ErrorImpl> {
+    fn erase(&self) -> RefPtr<'_, ErrorImpl> {
         // Erase the concrete type of E but preserve the vtable in self.vtable
         // for manipulating the resulting thin pointer. This is analogous to an
         // unsize coercion.
@@ -783,14 +1702,14 @@ impl<E> ErrorImpl<E> {
 }
 
 impl ErrorImpl {
-    pub(crate) unsafe fn error(this: RefPtr<Self>) -> &(dyn StdError + Send + Sync + 'static) {
+    pub(crate) unsafe fn error(this: RefPtr<'_, Self>) -> &(dyn StdError + Send + Sync + 'static) {
         // Use vtable to attach E's native StdError vtable for the right
         // original type E.
         unsafe { (vtable(this.ptr).object_ref)(this) }
     }
 
     pub(crate) unsafe fn error_mut(
-        this: MutPtr<Self>,
+        this: MutPtr<'_, Self>,
     ) -> &mut (dyn StdError + Send + Sync + 'static) {
         // Use vtable to attach E's native StdError vtable for the right
         // original type E.
@@ -801,11 +1720,11 @@ impl ErrorImpl {
     //     unsafe { (vtable(this.ptr).object_super)(this) }
     // }
 
-    pub(crate) unsafe fn backtrace(this: RefPtr<Self>) -> &Backtrace {
+    pub(crate) unsafe fn backtrace(this: RefPtr<'_, Self>) -> &Backtrace {
         // This unwrap can only panic if the underlying error's backtrace method
         // is nondeterministic, which would only happen in maliciously
         // constructed code.
-        this.as_ref()
+        unsafe { this.as_ref() }
             .backtrace
             .as_ref()
             .or_else(|| {
@@ -817,8 +1736,104 @@ impl ErrorImpl {
             .expect("backtrace capture failed")
     }
 
-    pub(crate) unsafe fn location(this: RefPtr<Self>) -> &Location {
-        &this.as_ref().location
+    pub(crate) unsafe fn location(this: RefPtr<Self>) -> &'static Location {
+        unsafe { this.as_ref().location }
+    }
+
+    pub(crate) unsafe fn location_mut(mut this: MutPtr<'_, Self>) -> &mut &'static Location {
+        unsafe { &mut this.as_mut().location }
+    }
+
+    pub(crate) unsafe fn suppressed(this: RefPtr<'_, Self>) -> &[Error] {
+        unsafe { this.as_ref() }
+            .origin
+            .as_ref()
+            .map_or(&[], |origin| &origin.suppressed)
+    }
+
+    pub(crate) unsafe fn suppressed_mut(mut this: MutPtr<'_, Self>) -> &mut Vec<Error> {
+        let origin = unsafe { &mut this.as_mut().origin };
+        &mut origin
+            .get_or_insert_with(|| Box::new(Origin::suppressed(Vec::new())))
+            .suppressed
+    }
+
+    // The wallee::Error that this layer was built on top of, if this layer is
+    // context attached to an existing wallee::Error.
+    pub(crate) unsafe fn inner(this: RefPtr<'_, Self>) -> Option<&Error> {
+        unsafe { (vtable(this.ptr).object_inner)(this) }
+    }
+
+    // The layer where the error originated, underneath any context.
+    pub(crate) unsafe fn origin(this: RefPtr<Self>) -> RefPtr<Self> {
+        let mut layer = this;
+        while let Some(inner) = unsafe { Self::inner(layer) } {
+            layer = inner.inner.as_ref();
+        }
+        layer
+    }
+
+    // The types of the context and of the error or message held by this
+    // layer.
+    pub(crate) unsafe fn type_names(this: RefPtr<Self>) -> TypeNames {
+        (unsafe { vtable(this.ptr) }.object_type_names)()
+    }
+
+    // Only the layer where the error originated gets an ID, a thread, a time,
+    // the active scopes, the breadcrumbs and a span trace. Layers built on
+    // another wallee::Error defer to it, as for the backtrace.
+    unsafe fn capture_origin(mut this: OwnPtr<Self>) {
+        if unsafe { Self::inner(this.as_ref()) }.is_some() {
+            return;
+        }
+        unsafe { this.deref_mut() }.origin = origin::capture();
+    }
+
+    // What was captured where the error originated, if anything was.
+    unsafe fn captured(this: RefPtr<'_, Self>) -> Option<&Origin> {
+        let origin = unsafe { Self::origin(this) };
+        unsafe { origin.as_ref() }.origin.as_deref()
+    }
+
+    pub(crate) unsafe fn id(this: RefPtr<Self>) -> Option<ErrorId> {
+        unsafe { Self::captured(this) }.and_then(|origin| origin.id)
+    }
+
+    pub(crate) unsafe fn thread(this: RefPtr<'_, Self>) -> Option<&Thread> {
+        unsafe { Self::captured(this) }.and_then(|origin| origin.thread.as_ref())
+    }
+
+    pub(crate) unsafe fn created_at(this: RefPtr<Self>) -> Option<SystemTime> {
+        unsafe { Self::captured(this) }.and_then(|origin| origin.created_at)
+    }
+
+    // Whether this layer is a scope attached as context.
+    pub(crate) unsafe fn is_scope(this: RefPtr<Self>) -> bool {
+        let scope = TypeId::of::<ScopeFrame>();
+        unsafe { Self::downcast_local(this, scope) }.is_some()
+    }
+
+    pub(crate) unsafe fn breadcrumbs(this: RefPtr<'_, Self>) -> &[Breadcrumb] {
+        unsafe { Self::captured(this) }.map_or(&[], |origin| &origin.breadcrumbs)
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) unsafe fn span_trace(this: RefPtr<'_, Self>) -> Option<&tracing_error::SpanTrace> {
+        unsafe { Self::captured(this) }.and_then(|origin| origin.span_trace.as_ref())
+    }
+
+    // Like the vtable's object_downcast, but only for the values owned by this
+    // layer itself, not by the wallee::Error that it may have been built on.
+    pub(crate) unsafe fn downcast_local(this: RefPtr<Self>, target: TypeId) -> Option<OwnPtr<()>> {
+        let addr =
+            unsafe { (vtable(this.ptr).object_downcast)(OwnPtr::from_raw(this.ptr), target) }?;
+        if let Some(inner) = unsafe { Self::inner(this) } {
+            let deeper = unsafe { (vtable(inner.inner.ptr).object_downcast)(inner.inner, target) };
+            if deeper.is_some_and(|deeper| deeper.as_ptr() == addr.as_ptr()) {
+                return None;
+            }
+        }
+        Some(addr)
     }
 
     #[cfg(error_generic_member_access)]
@@ -831,7 +1846,12 @@ impl ErrorImpl {
 
     #[cold]
     pub(crate) unsafe fn chain(this: RefPtr<Self>) -> Chain {
-        Chain::new(unsafe { Self::error(this) })
+        if !unsafe { Self::has_attachments(this) } {
+            return Chain::new(unsafe { Self::error(this) });
+        }
+        // Without the HttpStatus and ProblemExtension attachments.
+        let links = unsafe { chain::links(this) };
+        Chain::buffered(links.into_iter().map(|link| link.error).collect())
     }
 }
 
@@ -874,6 +1894,55 @@ impl From<Error> for Box<dyn StdError + Send + Sync + 'static> {
     }
 }
 
+#[cfg(feature = "anyhow")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "anyhow")))]
+impl From<Error> for anyhow::Error {
+    /// The error is wrapped in a [`SharedError`], to which the anyhow error
+    /// can be downcast to get at the wallee location. The message of the
+    /// anyhow error stays that of the wallee error. An error that had been
+    /// converted from anyhow in the first place is unwrapped instead.
+    #[cold]
+    fn from(error: Error) -> Self {
+        use crate::wrapper::AnyhowError;
+        let this = error.inner.as_ref();
+        let converted = unsafe { ErrorImpl::error(this) }.is::<AnyhowError>()
+            && unsafe { ErrorImpl::suppressed(this) }.is_empty();
+        if converted {
+            if let Ok(error) = error.downcast::<anyhow::Error>() {
+                return error;
+            }
+            unreachable!()
+        }
+        anyhow::Error::new(error.share())
+    }
+}
+
+#[cfg(feature = "eyre")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "eyre")))]
+impl From<Error> for eyre::Report {
+    /// The error is wrapped in a [`SharedError`], to which the report can be
+    /// downcast. The report handler is told the location where the error
+    /// originated rather than where it was converted. A report that had been
+    /// converted into a wallee error in the first place is unwrapped instead.
+    #[cold]
+    fn from(error: Error) -> Self {
+        use crate::wrapper::EyreError;
+        let this = error.inner.as_ref();
+        let converted = unsafe { ErrorImpl::error(this) }.is::<EyreError>()
+            && unsafe { ErrorImpl::suppressed(this) }.is_empty();
+        if converted {
+            if let Ok(report) = error.downcast::<eyre::Report>() {
+                return report;
+            }
+            unreachable!()
+        }
+        let location = error.location();
+        let mut report = eyre::Report::new(error.share());
+        report.handler_mut().track_caller(location);
+        report
+    }
+}
+
 impl From<Error> for Box<dyn StdError + Send + 'static> {
     fn from(error: Error) -> Self {
         Box::<dyn StdError + Send + Sync>::from(error)
@@ -0,0 +1,144 @@
+use crate::error::ErrorImpl;
+use crate::location::Location;
+use crate::ptr::RefPtr;
+use crate::wrapper::BoxedError;
+use crate::{Error, Found, SharedError, StdError};
+use core::any::TypeId;
+use core::ops::{Deref, DerefMut};
+
+impl<T> Found<T> {
+    pub(crate) fn new(value: T, location: &'static Location) -> Self {
+        Found { value, location }
+    }
+
+    /// The location of the nearest `wallee::Error` layer that holds the
+    /// value, either directly or somewhere in its `source()` chain.
+    pub fn location(&self) -> &'static Location {
+        self.location
+    }
+
+    /// Discard the location, keeping only the reference to the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Found<T>
+where
+    T: Deref,
+{
+    type Target = T::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Found<T>
+where
+    T: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+// Visits every value of type E held by `error`, outermost first, until `visit`
+// returns false. Each wallee layer is searched for the values it owns directly
+// (its error, message or context) and then along the source() links of its
+// error, up to the point where the next wallee layer begins.
+pub(crate) fn search<'a, E>(
+    error: &'a Error,
+    mut visit: impl FnMut(&'a E, &'static Location) -> bool,
+) where
+    E: StdError + 'static,
+{
+    let target = TypeId::of::<E>();
+    let mut layer = Some(error.inner.as_ref());
+
+    while let Some(this) = layer {
+        let location = unsafe { ErrorImpl::location(this) };
+        let inner = unsafe { ErrorImpl::inner(this) };
+
+        let owned = unsafe { ErrorImpl::downcast_local(this, target) }
+            .map(|addr| unsafe { addr.cast::<E>().deref() });
+        if let Some(owned) = owned {
+            if !visit(owned, location) {
+                return;
+            }
+        }
+
+        let mut link: Option<&(dyn StdError + 'static)> = Some(unsafe { ErrorImpl::error(this) });
+        while let Some(error) = link {
+            let found = downcast_link::<E>(error);
+            if let Some(found) = found {
+                let duplicate = owned.is_some_and(|owned| address(owned) == address(found));
+                if !duplicate && !visit(found, location) {
+                    return;
+                }
+            }
+            // Past the layer's own error, the source() chain belongs to the
+            // wallee::Error that this layer was built on, if any.
+            link = match inner {
+                Some(_) => None,
+                None => error.source(),
+            };
+        }
+
+        layer = inner.map(|inner| inner.inner.as_ref());
+    }
+}
+
+// Like `search` but only for the values owned directly by each layer, which
+// are the only ones that can be handed out by mutable reference. The layers
+// of a shared error are owned by all of its clones, so the search stops there.
+pub(crate) fn search_mut<E>(error: &mut Error) -> Option<Found<&mut E>>
+where
+    E: 'static,
+{
+    let target = TypeId::of::<E>();
+    let shared = TypeId::of::<SharedError>();
+    let mut layer: Option<RefPtr<ErrorImpl>> = Some(error.inner.as_ref());
+
+    while let Some(this) = layer {
+        if let Some(addr) = unsafe { ErrorImpl::downcast_local(this, target) } {
+            let location = unsafe { ErrorImpl::location(this) };
+            return Some(Found::new(
+                unsafe { addr.cast::<E>().deref_mut() },
+                location,
+            ));
+        }
+        if unsafe { ErrorImpl::downcast_local(this, shared) }.is_some() {
+            break;
+        }
+        layer = unsafe { ErrorImpl::inner(this) }.map(|inner| inner.inner.as_ref());
+    }
+
+    None
+}
+
+// Looks through the wrappers that hold another error type's value.
+fn downcast_link<'a, E>(error: &'a (dyn StdError + 'static)) -> Option<&'a E>
+where
+    E: StdError + 'static,
+{
+    if let Some(boxed) = error.downcast_ref::<BoxedError>() {
+        return boxed.0.downcast_ref::<E>();
+    }
+    #[cfg(feature = "anyhow")]
+    if let Some(anyhow) = error.downcast_ref::<crate::wrapper::AnyhowError>() {
+        return (*anyhow.0).downcast_ref::<E>();
+    }
+    #[cfg(feature = "eyre")]
+    if let Some(eyre) = error.downcast_ref::<crate::wrapper::EyreError>() {
+        return (*eyre.0).downcast_ref::<E>();
+    }
+    error.downcast_ref::<E>()
+}
+
+fn address<T>(value: &T) -> *const ()
+where
+    T: ?Sized,
+{
+    (value as *const T).cast::<()>()
+}
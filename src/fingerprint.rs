@@ -0,0 +1,132 @@
+use crate::chain;
+use crate::{Dedup, Error};
+use alloc::format;
+use alloc::string::String;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+impl Error {
+    /// A hash identifying the kind of failure that this error represents, for
+    /// grouping identical errors.
+    ///
+    /// The hash covers the location of each error in the
+    /// [chain][Error::chain] and its type name, when wallee knows it, but not
+    /// the messages. Two errors that went through the same places in the code
+    /// have the same fingerprint, even if their messages mention different
+    /// values.
+    ///
+    /// The hash is FNV-1a, which is the same on every platform and every run
+    /// of a program. It changes when the code moves to another line, and may
+    /// change with the compiler version as type names are not stable.
+    ///
+    /// ```
+    /// use wallee::{bail, Result};
+    ///
+    /// fn open(path: &str) -> Result<()> {
+    ///     bail!("no such file: {}", path);
+    /// }
+    ///
+    /// let a = open("a.toml").unwrap_err();
+    /// let b = open("b.toml").unwrap_err();
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv::new();
+        for link in unsafe { chain::links(self.inner.as_ref()) } {
+            hasher.write(link.location.file().as_bytes());
+            hasher.write(&link.location.line().to_le_bytes());
+            hasher.write(&link.location.column().to_le_bytes());
+            hasher.write(link.type_name.unwrap_or("").as_bytes());
+        }
+        hasher.0
+    }
+
+    /// The [fingerprint][Error::fingerprint] as 16 lowercase hexadecimal
+    /// digits.
+    pub fn fingerprint_hex(&self) -> String {
+        format!("{:016x}", self.fingerprint())
+    }
+}
+
+// 64-bit FNV-1a.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    // Each field is followed by a byte that cannot appear in UTF-8, so that
+    // the boundaries between fields are part of the hash.
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes.iter().chain(&[0xff]) {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+pub(crate) struct Seen {
+    reported: Instant,
+    suppressed: u64,
+    total: u64,
+}
+
+impl Dedup {
+    /// A `Dedup` reporting each kind of error at most once per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Dedup {
+            interval,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record an occurrence of the error, and decide whether to report it.
+    ///
+    /// Returns `Some` with the number of identical errors suppressed since the
+    /// last report if this one should be reported, or `None` if it should be
+    /// suppressed.
+    pub fn check(&self, error: &Error) -> Option<u64> {
+        let fingerprint = error.fingerprint();
+        let now = Instant::now();
+        let mut seen = self
+            .seen
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        match seen.get_mut(&fingerprint) {
+            None => {
+                seen.insert(
+                    fingerprint,
+                    Seen {
+                        reported: now,
+                        suppressed: 0,
+                        total: 1,
+                    },
+                );
+                Some(0)
+            }
+            Some(entry) => {
+                entry.total += 1;
+                if now.duration_since(entry.reported) >= self.interval {
+                    entry.reported = now;
+                    Some(core::mem::take(&mut entry.suppressed))
+                } else {
+                    entry.suppressed += 1;
+                    None
+                }
+            }
+        }
+    }
+
+    /// The number of times an error with the same fingerprint as this one has
+    /// been [checked][Dedup::check], whether it was reported or not.
+    pub fn count(&self, error: &Error) -> u64 {
+        let fingerprint = error.fingerprint();
+        let seen = self
+            .seen
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        seen.get(&fingerprint).map_or(0, |entry| entry.total)
+    }
+}
@@ -1,15 +1,23 @@
-use crate::chain::Chain;
+use crate::chain;
 use crate::error::ErrorImpl;
+use crate::origin::{DisplayThread, DisplayTime};
 use crate::ptr::RefPtr;
+use crate::Suppressed;
 use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Whether the Debug representation shows the type name of each error.
+pub(crate) static TYPE_NAMES: AtomicBool = AtomicBool::new(false);
 
 impl ErrorImpl {
     pub(crate) unsafe fn display(this: RefPtr<Self>, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", unsafe { Self::error(this) })?;
+        let mut chain = unsafe { Self::chain(this) };
+        if let Some(error) = chain.next() {
+            write!(f, "{error}")?;
+        }
 
         if f.alternate() {
-            let chain = unsafe { Self::chain(this) };
-            for cause in chain.skip(1) {
+            for cause in chain {
                 write!(f, ": {cause}")?;
             }
         }
@@ -22,8 +30,25 @@ impl ErrorImpl {
         let location = unsafe { Self::location(this) };
 
         if f.alternate() {
-            return f
-                .debug_struct("Wallee")
+            let mut debug = f.debug_struct("Wallee");
+            // Nested layers of context share the ID of the innermost one.
+            let origin = unsafe { Self::inner(this) }.is_none();
+            if origin {
+                if let Some(id) = unsafe { Self::id(this) } {
+                    debug.field("id", &format_args!("{id}"));
+                }
+                if let Some(thread) = unsafe { Self::thread(this) } {
+                    debug.field("thread", &format_args!("{}", DisplayThread(thread)));
+                }
+                if let Some(created_at) = unsafe { Self::created_at(this) } {
+                    debug.field("created_at", &format_args!("{}", DisplayTime(created_at)));
+                }
+                let breadcrumbs = unsafe { Self::breadcrumbs(this) };
+                if !breadcrumbs.is_empty() {
+                    debug.field("breadcrumbs", &breadcrumbs);
+                }
+            }
+            debug
                 .field(
                     "location",
                     &format!(
@@ -33,23 +58,53 @@ impl ErrorImpl {
                         location.column()
                     ),
                 )
-                .field("error", &error)
-                .finish();
+                .field("error", &error);
+            // Suppressed on any layer, the same as the plain format lists.
+            let suppressed = Suppressed::new(this).collect::<Vec<_>>();
+            if !suppressed.is_empty() {
+                debug.field("suppressed", &suppressed);
+            }
+            return debug.finish();
         }
 
+        if let Some(id) = unsafe { Self::id(this) } {
+            write!(f, "[{id}] ")?;
+        }
+        // The chain leaves out attachments for the HTTP response, so its head
+        // may be a message beneath the outermost layer.
+        let mut chain = unsafe { Self::chain(this) };
+        let head = chain.next().unwrap_or(error);
         write!(
             f,
             "{}({}:{}): {}",
             location.file(),
             location.line(),
             location.column(),
-            error
+            head
         )?;
 
-        if let Some(cause) = error.source() {
+        let type_names: Vec<Option<&str>> = if TYPE_NAMES.load(Ordering::Relaxed) {
+            let links = unsafe { chain::links(this) };
+            links.into_iter().map(|link| link.type_name).collect()
+        } else {
+            Vec::new()
+        };
+        let type_name = |n: usize| type_names.get(n).copied().flatten();
+        if let Some(type_name) = type_name(0) {
+            write!(f, " [{type_name}]")?;
+        }
+
+        if let Some(thread) = unsafe { Self::thread(this) } {
+            write!(f, "\nThread: {}", DisplayThread(thread))?;
+        }
+        if let Some(created_at) = unsafe { Self::created_at(this) } {
+            write!(f, "\nCreated at: {}", DisplayTime(created_at))?;
+        }
+
+        if chain.len() > 0 {
             write!(f, "\n\nCaused by:")?;
-            let multiple = cause.source().is_some();
-            for (n, error) in Chain::new(cause).enumerate() {
+            let multiple = chain.len() > 1;
+            for (n, error) in chain.enumerate() {
                 writeln!(f)?;
                 let mut indented = Indented {
                     inner: f,
@@ -57,6 +112,62 @@ impl ErrorImpl {
                     started: false,
                 };
                 write!(indented, "{error}")?;
+                if let Some(type_name) = type_name(n + 1) {
+                    write!(indented, " [{type_name}]")?;
+                }
+            }
+        }
+
+        let mut suppressed = Suppressed::new(this).peekable();
+        if suppressed.peek().is_some() {
+            write!(f, "\n\nSuppressed:")?;
+            let multiple = suppressed.clone().nth(1).is_some();
+            for (n, error) in suppressed.enumerate() {
+                writeln!(f)?;
+                let mut indented = Indented {
+                    inner: f,
+                    number: if multiple { Some(n) } else { None },
+                    started: false,
+                };
+                write!(
+                    indented,
+                    "{}({}:{}): {:#}",
+                    error.file(),
+                    error.line(),
+                    error.column(),
+                    error
+                )?;
+            }
+        }
+
+        let breadcrumbs = unsafe { Self::breadcrumbs(this) };
+        if !breadcrumbs.is_empty() {
+            write!(f, "\n\nBreadcrumbs:")?;
+            let multiple = breadcrumbs.len() > 1;
+            // Oldest first, as they happened.
+            for (n, breadcrumb) in breadcrumbs.iter().enumerate() {
+                writeln!(f)?;
+                let mut indented = Indented {
+                    inner: f,
+                    number: if multiple { Some(n) } else { None },
+                    started: false,
+                };
+                let location = breadcrumb.location();
+                write!(
+                    indented,
+                    "{}({}:{}): {}",
+                    location.file(),
+                    location.line(),
+                    location.column(),
+                    breadcrumb.message()
+                )?;
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(span_trace) = unsafe { Self::span_trace(this) } {
+                write!(f, "\n\nSpan trace:\n{span_trace}")?;
             }
         }
 
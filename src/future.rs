@@ -0,0 +1,190 @@
+use crate::context::ext::StdError;
+use crate::{
+    ContextFuture, ContextStream, Error, FutureExt, TryStreamExt, WithContextFuture,
+    WithContextStream,
+};
+use core::fmt::Display;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+impl<F, T, E> FutureExt<T, E> for F
+where
+    F: Future<Output = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+{
+    fn context<C>(self, context: C) -> ContextFuture<Self, C>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        ContextFuture {
+            future: self,
+            context: Some(context),
+            location: caller!(),
+        }
+    }
+
+    fn with_context<C, G>(self, f: G) -> WithContextFuture<Self, G>
+    where
+        C: Display + Send + Sync + 'static,
+        G: FnOnce() -> C,
+    {
+        WithContextFuture {
+            future: self,
+            f: Some(f),
+            location: caller!(),
+        }
+    }
+}
+
+impl<F, T, E, C> Future for ContextFuture<F, C>
+where
+    F: Future<Output = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Safety: the future is never moved out of the pinned ContextFuture.
+        let this = unsafe { self.get_unchecked_mut() };
+        let result = match unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(result.map_err(|error| {
+            let context = this.context.take().expect("polled after completion");
+            error.ext_context(context).at(this.location)
+        }))
+    }
+}
+
+impl<F, T, E, C, G> Future for WithContextFuture<F, G>
+where
+    F: Future<Output = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+    G: FnOnce() -> C,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Safety: the future is never moved out of the pinned
+        // WithContextFuture.
+        let this = unsafe { self.get_unchecked_mut() };
+        let result = match unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(result.map_err(|error| {
+            let f = this.f.take().expect("polled after completion");
+            error.ext_context(f()).at(this.location)
+        }))
+    }
+}
+
+impl<S, T, E> TryStreamExt<T, E> for S
+where
+    S: Stream<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+{
+    fn context<C>(self, context: C) -> ContextStream<Self, C>
+    where
+        C: Display + Clone + Send + Sync + 'static,
+    {
+        ContextStream {
+            stream: self,
+            context,
+            location: caller!(),
+        }
+    }
+
+    fn with_context<C, G>(self, f: G) -> WithContextStream<Self, G>
+    where
+        C: Display + Send + Sync + 'static,
+        G: FnMut() -> C,
+    {
+        WithContextStream {
+            stream: self,
+            f,
+            location: caller!(),
+        }
+    }
+}
+
+impl<S, T, E, C> Stream for ContextStream<S, C>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Clone + Send + Sync + 'static,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        // Safety: the stream is never moved out of the pinned ContextStream.
+        let this = unsafe { self.get_unchecked_mut() };
+        let item = match unsafe { Pin::new_unchecked(&mut this.stream) }.poll_next(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(item.map(|result| {
+            result.map_err(|error| error.ext_context(this.context.clone()).at(this.location))
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, T, E, C, G> Stream for WithContextStream<S, G>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+    G: FnMut() -> C,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        // Safety: the stream is never moved out of the pinned
+        // WithContextStream.
+        let this = unsafe { self.get_unchecked_mut() };
+        let item = match unsafe { Pin::new_unchecked(&mut this.stream) }.poll_next(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(
+            item.map(|result| {
+                result.map_err(|error| error.ext_context((this.f)()).at(this.location))
+            }),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pub(crate) mod private {
+    use super::*;
+
+    pub trait Sealed<T, E> {}
+
+    impl<F, T, E> Sealed<T, E> for F
+    where
+        F: Future<Output = Result<T, E>>,
+        E: StdError,
+    {
+    }
+
+    pub trait SealedStream<T, E> {}
+
+    impl<S, T, E> SealedStream<T, E> for S
+    where
+        S: Stream<Item = Result<T, E>>,
+        E: StdError,
+    {
+    }
+}
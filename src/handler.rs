@@ -0,0 +1,39 @@
+use crate::{Error, EyreReport, StdError};
+use core::fmt::{self, Debug, Display};
+
+impl<'a> EyreReport<'a> {
+    pub(crate) fn new(error: &'a Error) -> Self {
+        // eyre has no public way to call the installed hook directly, so have
+        // it create a handler for a report of our own.
+        let mut handler = eyre::Report::new(Placeholder);
+        handler.handler_mut().track_caller(error.location());
+        EyreReport { error, handler }
+    }
+
+    fn object(&self) -> &(dyn StdError + 'static) {
+        self.error.as_ref()
+    }
+}
+
+impl Debug for EyreReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.handler.handler().debug(self.object(), f)
+    }
+}
+
+impl Display for EyreReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.handler.handler().display(self.object(), f)
+    }
+}
+
+#[derive(Debug)]
+struct Placeholder;
+
+impl Display for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("wallee::Error")
+    }
+}
+
+impl StdError for Placeholder {}
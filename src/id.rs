@@ -0,0 +1,70 @@
+use crate::error::ErrorImpl;
+use crate::{Error, ErrorId};
+use core::fmt::{self, Display};
+use core::hash::{BuildHasher, Hasher};
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::collections::hash_map::RandomState;
+use std::sync::OnceLock;
+
+// IDs have 24 bits, printed as 6 hexadecimal digits.
+const MASK: u32 = 0x00ff_ffff;
+
+pub(crate) static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+// The ID of the next error to originate, if IDs are enabled.
+pub(crate) fn next() -> Option<ErrorId> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    static SEED: OnceLock<u32> = OnceLock::new();
+    let seed = *SEED.get_or_init(|| {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(std::process::id());
+        hasher.finish() as u32
+    });
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    Some(ErrorId(scramble(seed.wrapping_add(n))))
+}
+
+// A bijection on 24-bit numbers, so that consecutive errors get IDs that look
+// unrelated while the first 2^24 errors of the process still get distinct IDs.
+fn scramble(mut x: u32) -> u32 {
+    x = x.wrapping_mul(0x9e37_79b1) & MASK;
+    x ^= x >> 12;
+    x = x.wrapping_mul(0x85eb_ca6b) & MASK;
+    x ^= x >> 11;
+    x
+}
+
+impl Error {
+    /// The ID of this error, if error IDs were [enabled][crate::set_error_ids]
+    /// when it originated.
+    ///
+    /// The ID belongs to the error where the chain originated, so it stays
+    /// the same as context is attached. It shows up in the `Debug`
+    /// representation of the error, and in the structured renderings such as
+    /// [`ProblemDetails`][crate::ProblemDetails], which lets a user quote it
+    /// to find the matching entry in the logs.
+    ///
+    /// ```
+    /// use wallee::{wallee, Context};
+    ///
+    /// wallee::set_error_ids(true);
+    /// let error = wallee!("disk full");
+    /// let id = error.id().unwrap();
+    /// let error = error.context("failed to save draft");
+    /// assert_eq!(error.id(), Some(id));
+    /// assert!(format!("{:?}", error).starts_with(&format!("[{}]", id)));
+    /// ```
+    pub fn id(&self) -> Option<ErrorId> {
+        unsafe { ErrorImpl::id(self.inner.as_ref()) }
+    }
+}
+
+impl Display for ErrorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "err-{:06x}", self.0)
+    }
+}
@@ -0,0 +1,119 @@
+use crate::context::ext::StdError;
+use crate::location::Location;
+use crate::{ContextEach, Error, IterContext};
+use core::fmt::Display;
+use core::iter::{FromIterator, FusedIterator};
+
+impl<I, T, E> IterContext<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+{
+    fn context_each<C, F>(self, f: F) -> ContextEach<Self, F>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnMut(usize) -> C,
+    {
+        ContextEach {
+            iter: self,
+            f,
+            index: 0,
+            location: caller!(),
+        }
+    }
+
+    fn try_collect_context<B, C, F>(self, f: F) -> Result<B, Error>
+    where
+        B: FromIterator<T>,
+        C: Display + Send + Sync + 'static,
+        F: FnMut(usize) -> C,
+    {
+        self.context_each(f).collect()
+    }
+
+    fn collect_all_context<B, C, F>(self, f: F) -> Result<B, Error>
+    where
+        B: FromIterator<T>,
+        C: Display + Send + Sync + 'static,
+        F: FnMut(usize) -> C,
+    {
+        let mut first: Option<Error> = None;
+        let collection = self
+            .context_each(f)
+            .filter_map(|result| match result {
+                Ok(ok) => Some(ok),
+                Err(error) => {
+                    match &mut first {
+                        Some(first) => first.add_suppressed(error),
+                        None => first = Some(error),
+                    }
+                    None
+                }
+            })
+            .collect();
+        match first {
+            Some(error) => Err(error),
+            None => Ok(collection),
+        }
+    }
+}
+
+impl<I, T, E, C, F> Iterator for ContextEach<I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+    F: FnMut(usize) -> C,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(item.map_err(|error| wrap(error, (self.f)(index), self.location)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, T, E, C, F> ExactSizeIterator for ContextEach<I, F>
+where
+    I: ExactSizeIterator<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+    F: FnMut(usize) -> C,
+{
+}
+
+impl<I, T, E, C, F> FusedIterator for ContextEach<I, F>
+where
+    I: FusedIterator<Item = Result<T, E>>,
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+    F: FnMut(usize) -> C,
+{
+}
+
+fn wrap<E, C>(error: E, context: C, location: &'static Location) -> Error
+where
+    E: StdError + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
+{
+    error.ext_context(context).at(location)
+}
+
+pub(crate) mod private {
+    use super::*;
+
+    pub trait Sealed<T, E> {}
+
+    impl<I, T, E> Sealed<T, E> for I
+    where
+        I: Iterator<Item = Result<T, E>>,
+        E: StdError,
+    {
+    }
+}
@@ -0,0 +1,89 @@
+// A minimal JSON writer for the payloads that wallee renders itself, so that
+// rendering them does not depend on serde.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+pub(crate) struct Object<'a> {
+    out: &'a mut String,
+    empty: bool,
+}
+
+impl<'a> Object<'a> {
+    pub(crate) fn new(out: &'a mut String) -> Self {
+        out.push('{');
+        Object { out, empty: true }
+    }
+
+    // Writes the key of a member, returning the output for its value.
+    pub(crate) fn key(&mut self, key: &str) -> &mut String {
+        if !self.empty {
+            self.out.push(',');
+        }
+        self.empty = false;
+        write_str(self.out, key);
+        self.out.push(':');
+        self.out
+    }
+
+    pub(crate) fn str(&mut self, key: &str, value: &str) {
+        write_str(self.key(key), value);
+    }
+
+    pub(crate) fn u64(&mut self, key: &str, value: u64) {
+        let _ = write!(self.key(key), "{value}");
+    }
+
+    pub(crate) fn finish(self) {
+        self.out.push('}');
+    }
+}
+
+#[cfg(feature = "sentry")]
+pub(crate) struct Array<'a> {
+    out: &'a mut String,
+    empty: bool,
+}
+
+#[cfg(feature = "sentry")]
+impl<'a> Array<'a> {
+    pub(crate) fn new(out: &'a mut String) -> Self {
+        out.push('[');
+        Array { out, empty: true }
+    }
+
+    // Returns the output for the next element.
+    pub(crate) fn item(&mut self) -> &mut String {
+        if !self.empty {
+            self.out.push(',');
+        }
+        self.empty = false;
+        self.out
+    }
+
+    pub(crate) fn str(&mut self, value: &str) {
+        write_str(self.item(), value);
+    }
+
+    pub(crate) fn finish(self) {
+        self.out.push(']');
+    }
+}
+
+pub(crate) fn write_str(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -31,7 +31,7 @@
 //
 // Since specialization is not stable yet, instead we rely on autoref behavior
 // of method resolution to perform tagged dispatch. Here we have two traits
-// AdhocKind and TraitKind that both have an wallee_kind() method. AdhocKind is
+// AdhocKind and TraitKind that both have an __wallee_kind() method. AdhocKind is
 // implemented whether or not the caller's type has a std error impl, while
 // TraitKind is implemented only when a std error impl does exist. The ambiguity
 // is resolved by AdhocKind requiring an extra autoref so that it has lower
@@ -42,9 +42,9 @@
 //     #[allow(unused_imports)]
 //     use $crate::__private::{AdhocKind, TraitKind};
 //     let error = $msg;
-//     (&error).wallee_kind().new(error)
+//     (&error).__wallee_kind().new(error)
 
-use crate::Error;
+use crate::{Error, HasLocation, Located};
 use core::fmt::{Debug, Display};
 
 use crate::StdError;
@@ -54,7 +54,7 @@ pub struct MakeDebug;
 #[doc(hidden)]
 pub trait DebugKind: Sized {
     #[inline]
-    fn wallee_kind(&self) -> MakeDebug {
+    fn __wallee_kind(&self) -> MakeDebug {
         MakeDebug
     }
 }
@@ -77,7 +77,7 @@ pub struct MakeStd;
 #[doc(hidden)]
 pub trait StdKind: Sized {
     #[inline]
-    fn wallee_kind(&self) -> MakeStd {
+    fn __wallee_kind(&self) -> MakeStd {
         MakeStd
     }
 }
@@ -100,7 +100,7 @@ pub struct MakeError;
 #[doc(hidden)]
 pub trait ErrorKind: Sized {
     #[inline]
-    fn wallee_kind(&self) -> MakeError {
+    fn __wallee_kind(&self) -> MakeError {
         MakeError
     }
 }
@@ -115,12 +115,49 @@ impl MakeError {
     }
 }
 
+pub struct MakeLocated;
+
+#[doc(hidden)]
+pub trait LocatedKind: Sized {
+    #[inline]
+    fn __wallee_kind(&self) -> MakeLocated {
+        MakeLocated
+    }
+}
+
+impl<E> LocatedKind for Located<E> where E: StdError + Send + Sync + 'static {}
+
+impl MakeLocated {
+    #[cold]
+    pub fn make<E>(self, error: Located<E>) -> Error
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Error::from(error)
+    }
+}
+
+// Not a trait: derive(Locate) gives the type an inherent `__wallee_kind`
+// method returning MakeLocate, which takes precedence over the trait methods
+// above.
+pub struct MakeLocate;
+
+impl MakeLocate {
+    #[cold]
+    pub fn make<E>(self, error: E) -> Error
+    where
+        E: StdError + HasLocation + Send + Sync + 'static,
+    {
+        Error::from_located(error)
+    }
+}
+
 pub struct Boxed;
 
 #[doc(hidden)]
 pub trait BoxedKind: Sized {
     #[inline]
-    fn wallee_kind(&self) -> Boxed {
+    fn __wallee_kind(&self) -> Boxed {
         Boxed
     }
 }
@@ -135,3 +172,51 @@ impl Boxed {
         Error::from_boxed(error, backtrace)
     }
 }
+
+#[cfg(feature = "anyhow")]
+pub struct Anyhow;
+
+#[cfg(feature = "anyhow")]
+#[doc(hidden)]
+pub trait AnyhowKind: Sized {
+    #[inline]
+    fn __wallee_kind(&self) -> Anyhow {
+        Anyhow
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl AnyhowKind for anyhow::Error {}
+
+#[cfg(feature = "anyhow")]
+impl Anyhow {
+    #[cold]
+    #[track_caller]
+    pub fn make(self, error: anyhow::Error) -> Error {
+        Error::from_anyhow(error)
+    }
+}
+
+#[cfg(feature = "eyre")]
+pub struct Eyre;
+
+#[cfg(feature = "eyre")]
+#[doc(hidden)]
+pub trait EyreKind: Sized {
+    #[inline]
+    fn __wallee_kind(&self) -> Eyre {
+        Eyre
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl EyreKind for eyre::Report {}
+
+#[cfg(feature = "eyre")]
+impl Eyre {
+    #[cold]
+    #[track_caller]
+    pub fn make(self, report: eyre::Report) -> Error {
+        Error::from_eyre(report)
+    }
+}
@@ -193,11 +193,51 @@
 //!   # }
 //!   ```
 //!
+//! - The `derive` feature provides [`derive(Locate)`][Locate], which gives an
+//!   error type a location field filled in by its constructors and kept when
+//!   it becomes a wallee `Error`, and the [`#[wallee::context]`][context]
+//!   attribute, which attaches context to the error returned by a function.
+//!
+//! - With the `anyhow` feature enabled, errors coming from crates built on
+//!   [anyhow] can be converted with [`Error::from_anyhow`], and a wallee
+//!   `Error` converts into `anyhow::Error` with `?` in code that still returns
+//!   `anyhow::Result`.
+//!
+//!   [anyhow]: https://github.com/dtolnay/anyhow
+//!
+//! - Likewise the `eyre` feature provides [`Error::from_eyre`] and a conversion
+//!   into `eyre::Report`, as well as [`Error::eyre_report`] for printing a
+//!   wallee `Error` with the installed eyre report handler.
+//!
+//! - The `tracing` feature captures a [`SpanTrace`] where each error
+//!   originates and prints it in the `Debug` report, and adds
+//!   [`Error::emit`] and [`TraceErr`] for recording errors as `tracing`
+//!   events.
+//!
+//!   [`SpanTrace`]: https://docs.rs/tracing-error/latest/tracing_error/struct.SpanTrace.html
+//!
+//! - For binaries using the [log] crate instead, the `log` feature adds
+//!   [`Error::log`], [`log_error!`] and [`LogErr`], which log errors at the
+//!   location where they originated.
+//!
+//!   [log]: https://docs.rs/log
+//!
+//! - The `sentry` feature adds [`Error::to_sentry_event`], which renders an
+//!   error as the JSON payload of a [Sentry] event without depending on the
+//!   Sentry SDK.
+//!
+//!   [Sentry]: https://develop.sentry.dev/sdk/data-model/event-payloads/
+//!
+//! - The `futures` feature adds [`FutureExt`] and [`TryStreamExt`], which
+//!   attach context to the errors of futures and streams of `Result`s, like
+//!   [`Context`] does for a `Result`.
+//!
 
 #![doc(html_root_url = "https://docs.rs/wallee/1.0.79")]
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
 #![deny(dead_code, unused_imports, unused_mut)]
 #![deny(unsafe_op_in_unsafe_fn)]
+#![allow(clippy::needless_doctest_main)]
 
 #[cfg(wallee_nightly_testing)]
 compile_error!("Build script probe failed to compile.");
@@ -210,14 +250,39 @@ mod backtrace;
 #[macro_use]
 mod location;
 
+mod breadcrumb;
 mod chain;
 mod context;
 mod ensure;
 mod error;
+mod find;
+mod fingerprint;
 mod fmt;
+#[cfg(feature = "futures")]
+mod future;
+#[cfg(feature = "eyre")]
+mod handler;
+mod id;
+mod iter;
+mod json;
 mod kind;
+mod locate;
+mod located;
+#[cfg(feature = "log")]
+mod logging;
 mod macros;
+mod origin;
+mod otel;
+mod problem;
 mod ptr;
+mod scope;
+#[cfg(feature = "sentry")]
+mod sentry;
+mod shared;
+mod suppressed;
+#[cfg(feature = "tracing")]
+mod trace;
+mod wrap_err;
 mod wrapper;
 
 use crate::error::ErrorImpl;
@@ -226,9 +291,104 @@ use core::fmt::Display;
 
 use std::error::Error as StdError;
 
+pub use crate::location::Location;
+pub use crate::scope::scope;
+
 #[doc(no_inline)]
 pub use wallee as format_err;
 
+/// Derive macro implementing [`HasLocation`] for an error type.
+///
+/// Every variant of the enum, or the struct, needs a field of type
+/// `&'static wallee::Location` marked `#[location]`. The derive generates:
+///
+/// - an impl of [`HasLocation`] returning that field;
+/// - a `#[track_caller]` `From` impl for each variant made of the location and
+///   a single field marked `#[locate(from)]`, so that `?` records the location;
+/// - with `#[locate(constructors)]` on the type, a `#[track_caller]`
+///   constructor per variant, named after the variant in snake case (`new`
+///   for a struct), which takes the other fields as arguments and fills in
+///   the location of its caller.
+///
+/// Errors of these types keep their own location when converted into a
+/// `wallee::Error` by [`wallee!`] or [`Error::from_located`]. The `?`
+/// operator and [`Error::new`], which accept any error type, record the
+/// location of the conversion instead.
+///
+/// # Example
+///
+/// ```
+/// use std::io;
+/// use thiserror::Error;
+/// use wallee::{HasLocation, Location, Locate};
+///
+/// #[derive(Error, Debug, Locate)]
+/// #[locate(constructors)]
+/// pub enum ConfigError {
+///     #[error("missing key {key:?}")]
+///     MissingKey {
+///         key: String,
+///         #[location]
+///         location: &'static Location,
+///     },
+///     #[error("failed to read config")]
+///     Read(
+///         #[source]
+///         #[locate(from)]
+///         io::Error,
+///         #[location] &'static Location,
+///     ),
+/// }
+///
+/// fn lookup(key: &str) -> Result<String, ConfigError> {
+///     Err(ConfigError::missing_key(key.to_owned()))
+/// }
+///
+/// fn load() -> wallee::Result<String> {
+///     lookup("port").map_err(wallee::Error::from_located)
+/// }
+///
+/// let error = load().unwrap_err();
+/// let config_error = error.downcast_ref::<ConfigError>().unwrap();
+/// assert_eq!(error.location(), config_error.location());
+/// ```
+#[cfg(feature = "derive")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "derive")))]
+pub use wallee_derive::Locate;
+
+/// Attribute macro adding context to the error returned by a function.
+///
+/// `#[wallee::context("...")]` takes the same arguments as `format!`, which may
+/// refer to the function's arguments. If the function returns an error, the
+/// formatted message is attached to it as [context][Context], with the location
+/// of the function definition as the location of the context.
+///
+/// The function must return `Result<T, wallee::Error>`. It may be `async`.
+///
+/// The message is only formatted if the function returns an error, after its
+/// body ran. Arguments that the body takes ownership of, which are those passed
+/// by value or by `&mut` reference, are cloned beforehand if the message refers
+/// to them, so they need to implement `Clone`.
+///
+/// # Example
+///
+/// ```
+/// use wallee::{bail, Result};
+///
+/// # struct User;
+/// #
+/// #[wallee::context("loading user {id}")]
+/// fn load_user(id: u64) -> Result<User> {
+///     bail!("no such row");
+/// }
+///
+/// let error = load_user(7).err().unwrap();
+/// assert_eq!(format!("{:#}", error), "loading user 7: no such row");
+/// ```
+#[cfg(feature = "derive")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "derive")))]
+pub use wallee_derive::context;
+
 /// The `Error` type, a wrapper around a dynamic error type.
 ///
 /// `Error` works a lot like `Box<dyn std::error::Error>`, but with these
@@ -352,6 +512,405 @@ pub struct Chain<'a> {
     state: crate::chain::ChainState<'a>,
 }
 
+/// Iterator of a chain of source errors together with their type names.
+///
+/// This type is the iterator returned by [`Error::typed_chain`]. It visits the
+/// same errors as [`Chain`], and gives the name of the concrete type of each
+/// one, as returned by [`core::any::type_name`], if wallee knows it. That is
+/// the case for the errors, messages and context values that wallee holds
+/// itself, but not for the errors reachable only through the
+/// [`source()`][std::error::Error::source] of another error.
+///
+/// # Example
+///
+/// ```
+/// use wallee::Error;
+///
+/// pub fn print_causes(error: &Error) {
+///     for (n, (type_name, cause)) in error.typed_chain().enumerate() {
+///         eprintln!("{}: {} [{}]", n, cause, type_name.unwrap_or("?"));
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TypedChain<'a> {
+    links: std::vec::IntoIter<crate::chain::Link<'a>>,
+}
+
+/// Iterator of the errors suppressed while propagating an error.
+///
+/// This type is the iterator returned by [`Error::suppressed`].
+///
+/// # Example
+///
+/// ```
+/// use wallee::Error;
+///
+/// pub fn log_suppressed(error: &Error) {
+///     for suppressed in error.suppressed() {
+///         eprintln!("also failed at {}:{}: {:#}", suppressed.file(), suppressed.line(), suppressed);
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Suppressed<'a> {
+    state: crate::suppressed::SuppressedState<'a>,
+}
+
+/// A value found inside of an error, along with the location of the
+/// `wallee::Error` layer that holds it.
+///
+/// This type is returned by [`Error::find`], [`Error::find_mut`] and
+/// [`Error::find_all`], and dereferences to the value that was found.
+///
+/// # Example
+///
+/// ```
+/// use wallee::Error;
+/// use std::io;
+///
+/// pub fn report_io_error(error: &Error) {
+///     if let Some(io_error) = error.find::<io::Error>() {
+///         let location = io_error.location();
+///         eprintln!("{:?} at {}:{}", io_error.kind(), location.file(), location.line());
+///     }
+/// }
+/// ```
+pub struct Found<T> {
+    value: T,
+    location: &'static Location,
+}
+
+/// A cheaply clonable, reference counted [`Error`].
+///
+/// `Error` is not `Clone`, so it cannot be handed out to several recipients
+/// as is. A `SharedError`, created with [`Error::share`], can be: cloning it
+/// only bumps a reference count. This is useful for a failure that many
+/// waiters need to observe, such as the result of a shared future or a
+/// cached failed load.
+///
+/// `SharedError` dereferences to the original `Error` for access to its chain,
+/// location and backtrace, and implements `std::error::Error` itself. Turning
+/// it back into an `Error`, whether with [`SharedError::into_error`], `?` or
+/// [`Error::new`], keeps the location where the original error originated.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use wallee::{bail, Result, SharedError};
+///
+/// struct ConfigCache {
+///     loaded: HashMap<String, Result<String, SharedError>>,
+/// }
+///
+/// impl ConfigCache {
+///     fn get(&mut self, path: &str) -> Result<String> {
+///         let entry = self
+///             .loaded
+///             .entry(path.to_owned())
+///             .or_insert_with(|| load(path).map_err(wallee::Error::share));
+///         Ok(entry.clone()?)
+///     }
+/// }
+///
+/// fn load(path: &str) -> Result<String> {
+///     bail!("failed to load {}", path);
+/// }
+/// #
+/// # let mut cache = ConfigCache { loaded: HashMap::new() };
+/// # let first = cache.get("app.toml").unwrap_err();
+/// # let second = cache.get("app.toml").unwrap_err();
+/// # assert_eq!(first.line(), second.line());
+/// ```
+#[derive(Clone)]
+pub struct SharedError {
+    inner: alloc::sync::Arc<Error>,
+}
+
+/// A typed error of type `E` together with the location where it originated.
+///
+/// `wallee::Error` erases the type of the error, which makes it a poor fit for
+/// the public API of a library whose callers need to match on the variants of
+/// its error type. `Located<E>` keeps `E` as is, and records the caller
+/// location and a backtrace when it is created. Thanks to its `From<E>` impl,
+/// the `?` operator records the location in a function returning
+/// `Result<T, Located<E>>`.
+///
+/// `Located<E>` dereferences to `E`. Converting it into a `wallee::Error`,
+/// whether with `?`, `From` or the [`Context`] trait, keeps the location where
+/// the error originated rather than the location of the conversion.
+///
+/// `Located<E>` does not implement `std::error::Error` itself, as that would
+/// prevent the conversion into `wallee::Error` from keeping the location.
+///
+/// # Example
+///
+/// ```
+/// use thiserror::Error;
+/// use wallee::Located;
+///
+/// #[derive(Error, Debug)]
+/// pub enum ParseError {
+///     #[error("unexpected end of input")]
+///     Eof,
+///     #[error("invalid digit {0:?}")]
+///     InvalidDigit(char),
+/// }
+///
+/// pub fn parse_digit(input: &str) -> Result<u32, Located<ParseError>> {
+///     let c = input.chars().next().ok_or(ParseError::Eof)?;
+///     Ok(c.to_digit(10).ok_or(ParseError::InvalidDigit(c))?)
+/// }
+///
+/// fn main() -> wallee::Result<()> {
+///     match parse_digit("x") {
+///         Err(error) if matches!(*error, ParseError::InvalidDigit(_)) => {
+///             println!("{} at line {}", error, error.location().line());
+///         }
+///         result => {
+///             result?;
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Located<E> {
+    error: E,
+    location: &'static Location,
+    backtrace: Option<crate::backtrace::Backtrace>,
+    origin: Option<alloc::boxed::Box<crate::origin::Origin>>,
+}
+
+/// A wallee [`Error`] rendered by the installed eyre report handler.
+///
+/// Returned by [`Error::eyre_report`]. The `Debug` and `Display` impls defer to
+/// the handler's, in the same way as those of an `eyre::Report`.
+#[cfg(feature = "eyre")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "eyre")))]
+pub struct EyreReport<'a> {
+    error: &'a Error,
+    handler: eyre::Report,
+}
+
+/// An [RFC 7807] problem details object describing an [`Error`], for the body
+/// of an HTTP error response.
+///
+/// The members are taken from the error as follows:
+///
+/// - `status` is the outermost [`HttpStatus`] found in the error, or 500 if
+///   there is none.
+/// - `title` is the reason phrase of the status.
+/// - `detail` is the message right beneath the outermost `HttpStatus`, which
+///   is the error that the status was attached to, but only for a 4xx status.
+///   The context attached on top of it, the causes underneath it, and
+///   anything about a server error stay internal.
+/// - The extension members are the `ProblemExtension`s found in the error,
+///   outermost first.
+///
+/// [`ProblemDetails::verbose`] also fills in the internal details, for use in
+/// development: the whole chain of messages as the `detail` and the error's
+/// location as a `location` extension member.
+///
+/// The `HttpStatus` and `ProblemExtension` values attached as context are not
+/// part of the error's [chain][Error::chain], so they do not show up in its
+/// `Display` and `Debug` representations or in logs.
+///
+/// The members are public so that `type` and `instance`, which wallee knows
+/// nothing about, can be filled in by the caller. [`to_json`] renders the
+/// object for a response body of type [`CONTENT_TYPE`].
+///
+/// [RFC 7807]: https://www.rfc-editor.org/rfc/rfc7807
+/// [`to_json`]: ProblemDetails::to_json
+/// [`CONTENT_TYPE`]: ProblemDetails::CONTENT_TYPE
+///
+/// # Example
+///
+/// ```
+/// use wallee::{wallee, Context, HttpStatus, ProblemDetails, ProblemExtension};
+///
+/// let error = wallee!("no row with id 42")
+///     .context("user 42 does not exist")
+///     .context(HttpStatus(404))
+///     .context(ProblemExtension::new("user_id", 42));
+///
+/// let mut problem = ProblemDetails::new(&error);
+/// problem.instance = Some("/users/42".to_owned());
+/// assert_eq!(
+///     problem.to_json(),
+///     r#"{"type":"about:blank","title":"Not Found","status":404,"detail":"user 42 does not exist","instance":"/users/42","user_id":"42"}"#,
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProblemDetails {
+    /// A URI reference identifying the problem type, `about:blank` by
+    /// default.
+    pub type_uri: String,
+    /// A short summary of the problem type.
+    pub title: String,
+    /// The HTTP status code.
+    pub status: u16,
+    /// An explanation specific to this occurrence of the problem.
+    pub detail: Option<String>,
+    /// A URI reference identifying this occurrence of the problem.
+    pub instance: Option<String>,
+    /// Extension members, rendered as strings.
+    pub extensions: Vec<(String, String)>,
+}
+
+/// The HTTP status of an error, for [`ProblemDetails`].
+///
+/// Attach it as context to the error that should be reported with this
+/// status, or return it as the error itself.
+///
+/// ```
+/// use wallee::{Context, HttpStatus, Result};
+///
+/// fn parse_page(query: &str) -> Result<u32> {
+///     let page = query.parse::<u32>().context("page must be a number");
+///     page.context(HttpStatus(400))
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HttpStatus(pub u16);
+
+/// An extension member of [`ProblemDetails`], attached to an error as context.
+#[derive(Clone, Debug)]
+pub struct ProblemExtension {
+    name: String,
+    value: String,
+}
+
+/// A short ID identifying an error within the process, such as `err-7f3a9c`.
+///
+/// Errors get an ID when they originate if IDs are turned on with
+/// [`set_error_ids`]. See [`Error::id`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorId(u32);
+
+/// An event recorded by [`breadcrumb!`] before an error originated.
+///
+/// Returned by [`Error::breadcrumbs`].
+#[derive(Clone, Debug)]
+pub struct Breadcrumb {
+    message: alloc::sync::Arc<str>,
+    location: &'static Location,
+}
+
+/// A [scope][scope()] that was active where an error originated.
+///
+/// Attached to the error as context, and returned by [`Error::scopes`].
+#[derive(Clone, Debug)]
+pub struct ScopeFrame {
+    message: String,
+    location: &'static Location,
+}
+
+/// Future returned by [`InScope::in_scope`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ScopeFuture<F> {
+    future: F,
+    frame: (
+        alloc::sync::Arc<dyn Display + Send + Sync>,
+        &'static Location,
+    ),
+}
+
+/// Future returned by [`FutureExt::context`].
+#[cfg(feature = "futures")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ContextFuture<F, C> {
+    future: F,
+    context: Option<C>,
+    location: &'static Location,
+}
+
+/// Future returned by [`FutureExt::with_context`].
+#[cfg(feature = "futures")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WithContextFuture<F, G> {
+    future: F,
+    f: Option<G>,
+    location: &'static Location,
+}
+
+/// Stream returned by [`TryStreamExt::context`].
+#[cfg(feature = "futures")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+#[must_use = "streams do nothing unless polled"]
+pub struct ContextStream<S, C> {
+    stream: S,
+    context: C,
+    location: &'static Location,
+}
+
+/// Stream returned by [`TryStreamExt::with_context`].
+#[cfg(feature = "futures")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+#[must_use = "streams do nothing unless polled"]
+pub struct WithContextStream<S, G> {
+    stream: S,
+    f: G,
+    location: &'static Location,
+}
+
+/// Iterator returned by [`IterContext::context_each`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ContextEach<I, F> {
+    iter: I,
+    f: F,
+    index: usize,
+    location: &'static Location,
+}
+
+/// Rate limiter for reporting errors, keyed by their
+/// [fingerprint][Error::fingerprint].
+///
+/// The first occurrence of an error is reported, and identical errors are then
+/// only counted until the interval has elapsed since the last report. This
+/// keeps an error that repeats in a hot loop from flooding the logs, while the
+/// count of suppressed occurrences still shows how often it happened.
+///
+/// A `Dedup` remembers every fingerprint it has seen, so it is meant for
+/// errors coming from a bounded set of places in the code, which is what the
+/// fingerprint captures.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wallee::{bail, Dedup, Result};
+///
+/// fn poll() -> Result<()> {
+///     bail!("connection reset");
+/// }
+///
+/// let dedup = Dedup::new(Duration::from_secs(60));
+/// for _ in 0..1000 {
+///     if let Err(error) = poll() {
+///         if let Some(suppressed) = dedup.check(&error) {
+///             eprintln!("{:#} ({} identical errors suppressed)", error, suppressed);
+///         }
+///     }
+/// }
+/// ```
+pub struct Dedup {
+    interval: std::time::Duration,
+    seen: std::sync::Mutex<std::collections::HashMap<u64, crate::fingerprint::Seen>>,
+}
+
+/// The value of an attribute returned by [`Error::otel_attributes`].
+///
+/// The variants have the same names as those of `opentelemetry::Value`, so
+/// converting from one to the other is a plain `match`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OtelValue {
+    String(String),
+    I64(i64),
+}
+
 /// `Result<T, Error>`
 ///
 /// This is a reasonable return type to use throughout your application but also
@@ -565,6 +1124,369 @@ pub trait Context<T, E>: context::private::Sealed {
         F: FnOnce() -> C;
 }
 
+/// Provides the `context` and `with_context` methods for futures that resolve
+/// to a `Result`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// The context is attached to the error, if the future resolves to one, as
+/// [`Context`] would after the `.await`. The location of the context is the
+/// call to `context` or `with_context` rather than the place where the future
+/// is polled, which keeps it meaningful for futures passed to combinators and
+/// `select!`.
+///
+/// # Example
+///
+/// ```
+/// use wallee::{FutureExt, Result};
+///
+/// # async fn fetch(url: &str) -> Result<Vec<u8>> {
+/// #     unimplemented!()
+/// # }
+/// #
+/// async fn fetch_config(host: &str) -> Result<Vec<u8>> {
+///     let url = format!("https://{}/config.toml", host);
+///     fetch(&url)
+///         .with_context(|| format!("failed to fetch {}", url))
+///         .await
+/// }
+/// ```
+#[cfg(feature = "futures")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+pub trait FutureExt<T, E>: future::private::Sealed<T, E> {
+    /// Wrap the error of the future with additional context.
+    #[track_caller]
+    fn context<C>(self, context: C) -> ContextFuture<Self, C>
+    where
+        C: Display + Send + Sync + 'static,
+        Self: Sized;
+
+    /// Wrap the error of the future with additional context that is
+    /// evaluated lazily only once an error does occur.
+    #[track_caller]
+    fn with_context<C, G>(self, f: G) -> WithContextFuture<Self, G>
+    where
+        C: Display + Send + Sync + 'static,
+        G: FnOnce() -> C,
+        Self: Sized;
+}
+
+/// Provides the `context` and `with_context` methods for streams of `Result`s.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// Every error yielded by the stream gets the context, with the location of
+/// the call to `context` or `with_context`.
+///
+/// # Example
+///
+/// ```
+/// use futures::stream::{self, Stream};
+/// use wallee::{Result, TryStreamExt};
+///
+/// fn rows() -> impl Stream<Item = Result<u32>> {
+///     stream::iter(vec![Ok(1), Err(wallee::wallee!("truncated row"))])
+///         .context("failed to read rows")
+/// }
+/// ```
+#[cfg(feature = "futures")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+pub trait TryStreamExt<T, E>: future::private::SealedStream<T, E> {
+    /// Wrap each error of the stream with additional context.
+    #[track_caller]
+    fn context<C>(self, context: C) -> ContextStream<Self, C>
+    where
+        C: Display + Clone + Send + Sync + 'static,
+        Self: Sized;
+
+    /// Wrap each error of the stream with additional context that is
+    /// evaluated lazily only for errors.
+    #[track_caller]
+    fn with_context<C, G>(self, f: G) -> WithContextStream<Self, G>
+    where
+        C: Display + Send + Sync + 'static,
+        G: FnMut() -> C,
+        Self: Sized;
+}
+
+/// Provides the `wrap_err` and `wrap_err_with` methods for `Result`s whose
+/// error type is not a `std::error::Error`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// [`Context`] requires the error to implement `std::error::Error`. This trait
+/// covers the other error types that [`wallee!`] accepts: `String`,
+/// `&'static str` and `Cow<'static, str>` messages, and boxed errors
+/// `Box<dyn Error + Send + Sync>`, whose source chain is preserved. A
+/// `wallee::Error` is kept as is. A `Box<dyn Error>` without `Send` and `Sync`
+/// is accepted too, and goes through [`Error::from_local`], which keeps only
+/// the messages of its chain.
+///
+/// Errors that implement `std::error::Error` take context through [`Context`],
+/// which keeps their source chain. Other types that only implement `Display`
+/// and `Debug` can be turned into an `Error` with [`Error::msg`] first.
+///
+/// # Example
+///
+/// ```
+/// use wallee::{Result, WrapErr};
+///
+/// fn parse_port(value: &str) -> Result<u16, String> {
+///     value.parse().map_err(|_| format!("invalid port {:?}", value))
+/// }
+///
+/// fn configure(value: &str) -> Result<u16> {
+///     parse_port(value).wrap_err("failed to read server settings")
+/// }
+/// #
+/// # let error = configure("http").unwrap_err();
+/// # assert_eq!(format!("{:#}", error), r#"failed to read server settings: invalid port "http""#);
+/// ```
+pub trait WrapErr<T, E>: wrap_err::private::Sealed {
+    /// Wrap the error value with additional context.
+    #[track_caller]
+    fn wrap_err<C>(self, context: C) -> Result<T, Error>
+    where
+        C: Display + Send + Sync + 'static;
+
+    /// Wrap the error value with additional context that is evaluated lazily
+    /// only once an error does occur.
+    #[track_caller]
+    fn wrap_err_with<C, F>(self, f: F) -> Result<T, Error>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+/// Provides methods attaching context that names the index of the failing
+/// item to the errors of an iterator of `Result`s.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// The context is built by a closure from the index of the item, counting
+/// from 0, only for the items that are errors. Its location is the call to
+/// the method, as for [`Context`].
+///
+/// # Example
+///
+/// ```
+/// use wallee::{IterContext, Result};
+///
+/// fn parse_rows(rows: &[&str]) -> Result<Vec<u32>> {
+///     rows.iter()
+///         .map(|row| row.parse::<u32>())
+///         .try_collect_context(|i| format!("failed to parse row {} ({:?})", i, rows[i]))
+/// }
+/// #
+/// # let error = parse_rows(&["1", "x"]).unwrap_err();
+/// # assert_eq!(error.to_string(), r#"failed to parse row 1 ("x")"#);
+/// ```
+pub trait IterContext<T, E>: iter::private::Sealed<T, E> {
+    /// Wrap the error of each item with context built from its index.
+    #[track_caller]
+    fn context_each<C, F>(self, f: F) -> ContextEach<Self, F>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnMut(usize) -> C,
+        Self: Sized;
+
+    /// Collect the items, stopping at the first error, which is wrapped with
+    /// context built from its index.
+    #[track_caller]
+    fn try_collect_context<B, C, F>(self, f: F) -> Result<B, Error>
+    where
+        B: core::iter::FromIterator<T>,
+        C: Display + Send + Sync + 'static,
+        F: FnMut(usize) -> C,
+        Self: Sized;
+
+    /// Collect the items, going through all of them even after an error.
+    ///
+    /// If any item is an error, the result is the first error, wrapped with
+    /// context built from its index, and the errors of the following items,
+    /// each with its own context, are [suppressed][Error::suppressed] by the
+    /// first one.
+    ///
+    /// ```
+    /// use wallee::{IterContext, Result};
+    ///
+    /// let rows = ["1", "x", "3", "y"];
+    /// let result: Result<Vec<u32>> = rows
+    ///     .iter()
+    ///     .map(|row| row.parse::<u32>())
+    ///     .collect_all_context(|i| format!("row {}", i));
+    /// let error = result.unwrap_err();
+    /// assert_eq!(error.to_string(), "row 1");
+    /// assert_eq!(error.suppressed().next().unwrap().to_string(), "row 3");
+    /// ```
+    #[track_caller]
+    fn collect_all_context<B, C, F>(self, f: F) -> Result<B, Error>
+    where
+        B: core::iter::FromIterator<T>,
+        C: Display + Send + Sync + 'static,
+        F: FnMut(usize) -> C,
+        Self: Sized;
+}
+
+/// Provides the `or_cleanup` method for `Result`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// <br>
+///
+/// # Example
+///
+/// ```
+/// use wallee::{Cleanup, Result};
+/// use std::fs;
+/// use std::path::Path;
+///
+/// # fn render() -> Result<Vec<u8>> {
+/// #     unimplemented!()
+/// # }
+/// #
+/// pub fn publish(tmp: &Path, dest: &Path) -> Result<()> {
+///     render()
+///         .and_then(|bytes| Ok(fs::write(tmp, bytes)?))
+///         .and_then(|()| Ok(fs::rename(tmp, dest)?))
+///         .or_cleanup(|| fs::remove_file(tmp))
+/// }
+/// ```
+///
+/// If removing the temporary file fails too, the returned error is still the
+/// original failure, and the report lists the cleanup failure separately.
+///
+/// ```console
+/// Error: src/main.rs(8:10): No such file or directory (os error 2)
+///
+/// Suppressed:
+///     src/main.rs(10:10): Permission denied (os error 13)
+/// ```
+pub trait Cleanup<T, E>: suppressed::private::Sealed {
+    /// Run a fallible cleanup step if this is an error, keeping the original
+    /// error and recording any failure of the cleanup step as
+    /// [suppressed][Error::suppressed].
+    ///
+    /// The cleanup step is not run if this is `Ok`.
+    #[track_caller]
+    fn or_cleanup<F, C>(self, cleanup: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> Result<(), C>,
+        C: Send + Sync + 'static,
+        Error: From<C>;
+}
+
+/// An error that knows the location where it originated.
+///
+/// Usually implemented with `#[derive(wallee::Locate)]`, available with the
+/// `derive` feature, which also makes `wallee::Error` adopt this location in
+/// place of the location of the conversion.
+///
+/// # Example
+///
+/// ```
+/// use wallee::{HasLocation, Located};
+///
+/// fn report<E: HasLocation + std::fmt::Display>(error: &E) {
+///     let location = error.location();
+///     eprintln!("{} ({}:{})", error, location.file(), location.line());
+/// }
+///
+/// report(&Located::new(std::fmt::Error));
+/// ```
+pub trait HasLocation {
+    /// The file, line and column where this error originated.
+    fn location(&self) -> &'static Location;
+}
+
+/// Provides the `trace_err` method for `Result`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// # Example
+///
+/// ```
+/// use wallee::{Result, TraceErr};
+///
+/// # fn refresh_cache() -> Result<()> {
+/// #     Ok(())
+/// # }
+/// #
+/// pub fn tick() {
+///     // A failed refresh is not fatal, but should show up in the logs.
+///     let _ = refresh_cache().trace_err();
+/// }
+/// ```
+#[cfg(feature = "tracing")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tracing")))]
+pub trait TraceErr: trace::private::Sealed {
+    /// Record a `tracing` event for the error, if this is one, as with
+    /// [`Error::emit`], and return the result unchanged.
+    #[must_use]
+    fn trace_err(self) -> Self;
+}
+
+/// Provides the `log_err` method for `Result`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `wallee`.
+///
+/// # Example
+///
+/// ```
+/// use wallee::{LogErr, Result};
+///
+/// # struct Settings;
+/// #
+/// # fn load_settings() -> Result<Settings> {
+/// #     Ok(Settings)
+/// # }
+/// #
+/// pub fn settings() -> Settings {
+///     load_settings().log_err().unwrap_or(Settings)
+/// }
+/// ```
+#[cfg(feature = "log")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "log")))]
+pub trait LogErr<T>: logging::private::Sealed {
+    /// Log the error, if this is one, at the `Error` level as with
+    /// [`Error::log`], and convert the result into an `Option`.
+    fn log_err(self) -> Option<T>;
+}
+
+/// Extension trait running a `Future` within a [scope][scope()].
+pub trait InScope: scope::private::Sealed + Sized {
+    /// Run this future within a scope described by `context`.
+    ///
+    /// The scope is entered every time the future is polled, so errors that
+    /// originate in it get the scope attached even across `.await` points,
+    /// whichever thread polls it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{bail, InScope, Result};
+    ///
+    /// async fn fetch(id: u32) -> Result<String> {
+    ///     bail!("connection reset");
+    /// }
+    ///
+    /// async fn sync_user(id: u32) -> Result<String> {
+    ///     fetch(id).in_scope(format!("syncing user {}", id)).await
+    /// }
+    /// ```
+    #[track_caller]
+    fn in_scope<C>(self, context: C) -> ScopeFuture<Self>
+    where
+        C: Display + Send + Sync + 'static;
+}
+
 /// Equivalent to Ok::<_, wallee::Error>(value).
 ///
 /// This simplifies creation of an wallee::Result in places where type inference
@@ -588,6 +1510,57 @@ pub fn Ok<T>(t: T) -> Result<T> {
     Result::Ok(t)
 }
 
+/// Show the type name of each error in the `Debug` representation of errors.
+///
+/// This is off by default. When turned on, the errors of the chain whose type
+/// is known, as for [`Error::typed_chain`], are followed by their type name in
+/// brackets. This tells apart causes that have the same message.
+///
+/// ```text
+/// src/main.rs(12:5): failed to read config [&str]
+///
+/// Caused by:
+///     No such file or directory (os error 2) [std::io::error::Error]
+/// ```
+pub fn set_debug_type_names(enabled: bool) {
+    crate::fmt::TYPE_NAMES.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Give every error an [`ErrorId`] from now on.
+///
+/// This is off by default. IDs are generated from a counter and a seed that
+/// is random for each process, so they are unique within the process, up to
+/// 2<sup>24</sup> errors, and unlikely to repeat across processes.
+pub fn set_error_ids(enabled: bool) {
+    crate::id::ENABLED.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Record the thread on which each error originates from now on.
+///
+/// This is off by default. The thread is returned by [`Error::thread`] and its
+/// name and ID are printed in the `Debug` representation, which helps reading
+/// the interleaved logs of a multi-threaded program.
+pub fn set_thread_capture(enabled: bool) {
+    crate::origin::THREAD.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Record the time at which each error originates from now on.
+///
+/// This is off by default. The time is returned by [`Error::created_at`] and
+/// printed in UTC in the `Debug` representation.
+pub fn set_time_capture(enabled: bool) {
+    crate::origin::TIME.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Keep the last `capacity` [breadcrumbs][breadcrumb!] of each thread.
+///
+/// The default is 32. Errors that originate from now on record at most this
+/// many breadcrumbs, and a capacity of 0 turns off the recording of
+/// breadcrumbs altogether.
+pub fn set_breadcrumb_capacity(capacity: usize) {
+    crate::breadcrumb::CAPACITY.store(capacity, core::sync::atomic::Ordering::Relaxed);
+}
+
 // Not public API. Referenced by macro-generated code.
 #[doc(hidden)]
 pub mod __private {
@@ -595,19 +1568,63 @@ pub mod __private {
     use alloc::fmt;
     use core::fmt::Arguments;
 
+    #[doc(hidden)]
+    pub use crate::breadcrumb::record as breadcrumb;
     #[doc(hidden)]
     pub use crate::ensure::{BothDebug, NotBothDebug};
+
+    #[doc(hidden)]
+    pub fn fn_context<T, F>(
+        result: Result<T, Error>,
+        context: F,
+        location: &'static crate::Location,
+    ) -> Result<T, Error>
+    where
+        F: FnOnce() -> alloc::string::String,
+    {
+        match result {
+            Result::Ok(ok) => Result::Ok(ok),
+            Err(error) => Err(error.context(context()).at(location)),
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn call_once<F, T>(f: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> Result<T, Error>,
+    {
+        f()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn async_body<F, T>(body: F) -> F
+    where
+        F: core::future::Future<Output = Result<T, Error>>,
+    {
+        body
+    }
+
     #[doc(hidden)]
     pub use alloc::format;
     #[doc(hidden)]
     pub use core::result::Result::Err;
     #[doc(hidden)]
-    pub use core::{concat, format_args, stringify};
+    pub use core::{concat, format_args, module_path, stringify};
 
     #[doc(hidden)]
     pub mod kind {
         #[doc(hidden)]
-        pub use crate::kind::{BoxedKind, DebugKind, ErrorKind, StdKind};
+        pub use crate::kind::{BoxedKind, DebugKind, ErrorKind, LocatedKind, MakeLocate, StdKind};
+
+        #[doc(hidden)]
+        #[cfg(feature = "anyhow")]
+        pub use crate::kind::AnyhowKind;
+
+        #[doc(hidden)]
+        #[cfg(feature = "eyre")]
+        pub use crate::kind::EyreKind;
     }
 
     #[doc(hidden)]
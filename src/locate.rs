@@ -0,0 +1,14 @@
+use crate::location::Location;
+use crate::{Error, HasLocation, Located};
+
+impl HasLocation for Error {
+    fn location(&self) -> &'static Location {
+        Error::location(self)
+    }
+}
+
+impl<E> HasLocation for Located<E> {
+    fn location(&self) -> &'static Location {
+        Located::location(self)
+    }
+}
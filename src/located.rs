@@ -0,0 +1,101 @@
+use crate::backtrace::Backtrace;
+use crate::location::Location;
+use crate::origin::{self, Capture};
+use crate::{Error, Located, StdError};
+use core::fmt::{self, Debug, Display};
+use core::ops::{Deref, DerefMut};
+
+impl<E> Located<E> {
+    /// Wrap an error, recording the location of the caller and capturing a
+    /// backtrace, along with the ID, thread, time, scopes and breadcrumbs
+    /// that a [`wallee::Error`][Error] records where it originates.
+    #[cold]
+    #[track_caller]
+    pub fn new(error: E) -> Self {
+        Located {
+            error,
+            location: caller!(),
+            backtrace: backtrace!(),
+            origin: origin::capture(),
+        }
+    }
+
+    /// Returns the caller location from which the error originated.
+    pub fn location(&self) -> &'static Location {
+        self.location
+    }
+
+    /// Get the backtrace captured when the error was created.
+    pub fn backtrace(&self) -> Option<&impl_backtrace!()> {
+        self.backtrace.as_ref()
+    }
+
+    /// Discard the location, backtrace and origin data, returning the error.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> From<E> for Located<E> {
+    #[cold]
+    #[track_caller]
+    fn from(error: E) -> Self {
+        Located::new(error)
+    }
+}
+
+impl<E> From<Located<E>> for Error
+where
+    E: StdError + Send + Sync + 'static,
+{
+    #[cold]
+    fn from(located: Located<E>) -> Self {
+        let Located {
+            error,
+            location,
+            backtrace,
+            origin,
+        } = located;
+        let backtrace: Option<Backtrace> = match backtrace {
+            Some(backtrace) => Some(backtrace),
+            None => backtrace_if_absent!(&error),
+        };
+        // The error originated where it was located, not here.
+        Error::from_std_with(error, backtrace, Capture::Given(origin)).at(location)
+    }
+}
+
+impl<E> Deref for Located<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.error
+    }
+}
+
+impl<E> DerefMut for Located<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.error
+    }
+}
+
+impl<E> Display for Located<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<E> Debug for Located<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Located")
+            .field("error", &self.error)
+            .field("location", &self.location)
+            .finish()
+    }
+}
@@ -1,7 +1,8 @@
+/// The file, line and column where an error originated.
 pub type Location = std::panic::Location<'static>;
 
 macro_rules! caller {
     () => {
-        *$crate::location::Location::caller()
+        $crate::location::Location::caller()
     };
 }
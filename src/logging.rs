@@ -0,0 +1,114 @@
+use crate::{Dedup, Error, LogErr};
+use core::fmt::{self, Arguments, Display};
+use log::{Level, Metadata, Record};
+
+impl Error {
+    /// Log this error with the `log` crate.
+    ///
+    /// The message of the record is the error together with its causes, as
+    /// printed by `{:#}`, preceded by the error's [ID][Error::id] if it has
+    /// one. The record's file and line are those where the error
+    /// originated rather than those of the call to this method, and its target
+    /// is `wallee`. The [`log_error!`][crate::log_error] macro logs the error
+    /// with the calling module as the target instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use log::Level;
+    /// use wallee::{wallee, Context};
+    ///
+    /// let error = wallee!("disk full").context("failed to save draft");
+    /// error.log(Level::Warn);
+    /// ```
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "log")))]
+    pub fn log(&self, level: Level) {
+        self.log_to(level, "wallee");
+    }
+
+    /// Log this error as [`Error::log`] does, with the given target.
+    ///
+    /// Nothing is formatted unless the installed logger is enabled for the
+    /// target at this level.
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "log")))]
+    pub fn log_to(&self, level: Level, target: &str) {
+        self.log_args(level, target, format_args!("{}", Message(self)));
+    }
+
+    fn log_args(&self, level: Level, target: &str, args: Arguments) {
+        if level > log::max_level() {
+            return;
+        }
+        let metadata = Metadata::builder().level(level).target(target).build();
+        let logger = log::logger();
+        if !logger.enabled(&metadata) {
+            return;
+        }
+        let location = self.location();
+        logger.log(
+            &Record::builder()
+                .metadata(metadata)
+                .args(args)
+                .file_static(Some(location.file()))
+                .line(Some(location.line()))
+                .build(),
+        );
+    }
+}
+
+// The message of a record: the error's ID, if it has one, followed by the
+// error and its causes.
+struct Message<'a>(&'a Error);
+
+impl Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(id) = self.0.id() {
+            write!(f, "[{}] ", id)?;
+        }
+        write!(f, "{:#}", self.0)
+    }
+}
+
+impl Dedup {
+    /// Log the error as [`Error::log`] does, unless it is
+    /// [suppressed][Dedup::check] as a repeat of an error logged recently.
+    ///
+    /// The message mentions how many identical errors were suppressed since
+    /// the last one that was logged, if any.
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "log")))]
+    pub fn log(&self, error: &Error, level: Level) {
+        match self.check(error) {
+            None => {}
+            Some(0) => error.log(level),
+            Some(suppressed) => error.log_args(
+                level,
+                "wallee",
+                format_args!(
+                    "{} ({} identical errors suppressed)",
+                    Message(error),
+                    suppressed,
+                ),
+            ),
+        }
+    }
+}
+
+impl<T> LogErr<T> for Result<T, Error> {
+    fn log_err(self) -> Option<T> {
+        match self {
+            Ok(ok) => Some(ok),
+            Err(error) => {
+                error.log(Level::Error);
+                None
+            }
+        }
+    }
+}
+
+pub(crate) mod private {
+    use crate::Error;
+
+    pub trait Sealed {}
+
+    impl<T> Sealed for Result<T, Error> {}
+}
@@ -201,7 +201,7 @@ macro_rules! wallee {
         $crate::__private::must_use({
             use $crate::__private::kind::*;
             let error = match $err {
-                error => (&error).wallee_kind().make(error),
+                error => (&error).__wallee_kind().make(error),
             };
             error
         })
@@ -210,7 +210,7 @@ macro_rules! wallee {
         $crate::__private::must_use({
             use $crate::__private::kind::*;
             let error = match $err {
-                error => (&error).wallee_kind().make(error),
+                error => (&error).__wallee_kind().make(error),
             };
             $crate::__private::format_context(error, $crate::__private::format_args!($context))
         })
@@ -218,7 +218,7 @@ macro_rules! wallee {
     ($err:expr, $fmt:literal, $($arg:tt)*) => {{
         use $crate::__private::kind::*;
         let error = match $err {
-            error => (&error).wallee_kind().make(error),
+            error => (&error).__wallee_kind().make(error),
         };
         error.context($crate::__private::format!($fmt, $($arg)*))
     }};
@@ -226,7 +226,7 @@ macro_rules! wallee {
         $crate::__private::must_use({
             use $crate::__private::kind::*;
             let error = match $err {
-                error => (&error).wallee_kind().make(error),
+                error => (&error).__wallee_kind().make(error),
             };
             error.context($context)
         })
@@ -246,7 +246,7 @@ macro_rules! __wallee {
     ($err:expr $(,)?) => ({
         use $crate::__private::kind::*;
         let error = match $err {
-            error => (&error).wallee_kind().make(error),
+            error => (&error).__wallee_kind().make(error),
         };
         error
     });
@@ -254,3 +254,64 @@ macro_rules! __wallee {
         $crate::Error::msg($crate::__private::format!($fmt, $($arg)*))
     };
 }
+
+/// Record a breadcrumb, an event that is worth knowing about if an error
+/// happens soon after.
+///
+/// This takes a format string with arguments, like `format!`. The message and
+/// the location of the call go into a ring buffer of the current thread,
+/// which keeps the most recent breadcrumbs up to a
+/// [capacity][crate::set_breadcrumb_capacity]. Every
+/// [`Error`][crate::Error] that originates on the thread afterwards takes a
+/// snapshot of the buffer, listed under "Breadcrumbs:" in its `Debug`
+/// representation, which gives a cheap timeline of what happened just before
+/// the failure.
+///
+/// # Example
+///
+/// ```
+/// use std::path::Path;
+/// use wallee::{breadcrumb, ensure, Result};
+///
+/// fn load(path: &Path) -> Result<()> {
+///     breadcrumb!("opened {}", path.display());
+///     let header = "";
+///     breadcrumb!("read header of {} bytes", header.len());
+///     ensure!(!header.is_empty(), "missing header");
+///     Ok(())
+/// }
+///
+/// let error = load(Path::new("db.bin")).unwrap_err();
+/// assert_eq!(error.breadcrumbs().len(), 2);
+/// ```
+#[macro_export]
+macro_rules! breadcrumb {
+    ($($arg:tt)*) => {
+        $crate::__private::breadcrumb($crate::__private::format_args!($($arg)*))
+    };
+}
+
+/// Log an error with the `log` crate, targeted at the calling module.
+///
+/// `log_error!(error, level)` logs the [`Error`][crate::Error] as
+/// [`Error::log`][crate::Error::log] does, except that the target of the
+/// record is the module path of the call, as for the `log` crate's own
+/// macros, so that loggers can filter the record by module.
+///
+/// # Example
+///
+/// ```
+/// use log::Level;
+/// use wallee::{log_error, wallee};
+///
+/// let error = wallee!("disk full");
+/// log_error!(error, Level::Warn);
+/// ```
+#[cfg(feature = "log")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "log")))]
+#[macro_export]
+macro_rules! log_error {
+    ($error:expr, $level:expr $(,)?) => {
+        ($error).log_to($level, $crate::__private::module_path!())
+    };
+}
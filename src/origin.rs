@@ -0,0 +1,197 @@
+use crate::error::ErrorImpl;
+use crate::{breadcrumb, id, scope};
+use crate::{Breadcrumb, Error, ErrorId, ScopeFrame};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, Thread};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) static THREAD: AtomicBool = AtomicBool::new(false);
+pub(crate) static TIME: AtomicBool = AtomicBool::new(false);
+
+// What is recorded about the place where an error originated, along with the
+// errors suppressed on a layer. It is boxed apart from the error, and only
+// allocated if anything was captured or suppressed, so that errors stay small
+// while none of the captures are turned on. The scopes only stay here until
+// they are attached to the error as context. Layers of context only ever use
+// the suppressed errors.
+pub(crate) struct Origin {
+    pub id: Option<ErrorId>,
+    pub thread: Option<Thread>,
+    pub created_at: Option<SystemTime>,
+    pub scopes: Vec<ScopeFrame>,
+    pub breadcrumbs: Vec<Breadcrumb>,
+    #[cfg(feature = "tracing")]
+    pub span_trace: Option<tracing_error::SpanTrace>,
+    pub suppressed: Vec<Error>,
+}
+
+// Whether a new error captures its origin data where it is constructed, or
+// is given the data of the error that it is rebuilt from.
+pub(crate) enum Capture {
+    Here,
+    Given(Option<Box<Origin>>),
+}
+
+pub(crate) fn capture() -> Option<Box<Origin>> {
+    let origin = Origin {
+        id: id::next(),
+        thread: capture_thread(),
+        created_at: capture_time(),
+        scopes: scope::snapshot(),
+        breadcrumbs: breadcrumb::snapshot(),
+        #[cfg(feature = "tracing")]
+        span_trace: capture_span_trace(),
+        suppressed: Vec::new(),
+    };
+    if origin.is_empty() {
+        None
+    } else {
+        Some(Box::new(origin))
+    }
+}
+
+impl Origin {
+    // An origin holding only errors suppressed on a layer of context.
+    pub fn suppressed(suppressed: Vec<Error>) -> Self {
+        Origin {
+            id: None,
+            thread: None,
+            created_at: None,
+            scopes: Vec::new(),
+            breadcrumbs: Vec::new(),
+            #[cfg(feature = "tracing")]
+            span_trace: None,
+            suppressed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let empty = self.id.is_none()
+            && self.thread.is_none()
+            && self.created_at.is_none()
+            && self.scopes.is_empty()
+            && self.breadcrumbs.is_empty()
+            && self.suppressed.is_empty();
+        #[cfg(feature = "tracing")]
+        let empty = empty && self.span_trace.is_none();
+        empty
+    }
+}
+
+fn capture_thread() -> Option<Thread> {
+    if THREAD.load(Ordering::Relaxed) {
+        Some(thread::current())
+    } else {
+        None
+    }
+}
+
+fn capture_time() -> Option<SystemTime> {
+    if TIME.load(Ordering::Relaxed) {
+        Some(SystemTime::now())
+    } else {
+        None
+    }
+}
+
+// Only kept if the subscriber records spans, that is if it includes
+// tracing_error::ErrorLayer.
+#[cfg(feature = "tracing")]
+fn capture_span_trace() -> Option<tracing_error::SpanTrace> {
+    use tracing_error::{SpanTrace, SpanTraceStatus};
+
+    let span_trace = SpanTrace::capture();
+    if span_trace.status() == SpanTraceStatus::CAPTURED {
+        Some(span_trace)
+    } else {
+        None
+    }
+}
+
+impl Error {
+    /// The thread on which this error originated, if the capture of threads
+    /// was [turned on][crate::set_thread_capture] at the time.
+    ///
+    /// The thread stays the same as context is attached, wherever that
+    /// happens.
+    ///
+    /// ```
+    /// use std::thread;
+    /// use wallee::wallee;
+    ///
+    /// wallee::set_thread_capture(true);
+    /// let error = thread::Builder::new()
+    ///     .name("worker-1".to_owned())
+    ///     .spawn(|| wallee!("disk full"))
+    ///     .unwrap()
+    ///     .join()
+    ///     .unwrap();
+    /// assert_eq!(error.thread().unwrap().name(), Some("worker-1"));
+    /// ```
+    pub fn thread(&self) -> Option<&Thread> {
+        unsafe { ErrorImpl::thread(self.inner.as_ref()) }
+    }
+
+    /// The time at which this error originated, if the capture of times was
+    /// [turned on][crate::set_time_capture] at the time.
+    ///
+    /// The time stays the same as context is attached.
+    pub fn created_at(&self) -> Option<SystemTime> {
+        unsafe { ErrorImpl::created_at(self.inner.as_ref()) }
+    }
+}
+
+// The name and ID of a thread, as printed by the Debug representation.
+pub(crate) struct DisplayThread<'a>(pub &'a Thread);
+
+impl Display for DisplayThread<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.0.name().unwrap_or("<unnamed>");
+        write!(f, "'{}' ({:?})", name, self.0.id())
+    }
+}
+
+// A time as an RFC 3339 timestamp in UTC with microseconds, such as
+// 2024-05-01T12:34:56.789012Z.
+pub(crate) struct DisplayTime(pub SystemTime);
+
+impl Display for DisplayTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let since_epoch = match self.0.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch,
+            Err(_) => return write!(f, "{:?}", self.0),
+        };
+        let secs = since_epoch.as_secs();
+        let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+        let (year, month, day) = civil_from_days(days);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60,
+            since_epoch.subsec_micros(),
+        )
+    }
+}
+
+// The proleptic Gregorian date of a number of days since 1970-01-01, from
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
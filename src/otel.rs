@@ -0,0 +1,115 @@
+use crate::error::ErrorImpl;
+use crate::{Error, OtelValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+impl Error {
+    /// The attributes of this error according to the OpenTelemetry semantic
+    /// conventions for exceptions.
+    ///
+    /// | Key                    | Value                                              |
+    /// |------------------------|----------------------------------------------------|
+    /// | `exception.type`       | Type name of the error where the chain originated, if known |
+    /// | `exception.message`    | Message of the outermost error, as printed by `{}` |
+    /// | `exception.stacktrace` | The report printed by `{:?}`: location, causes and backtrace |
+    /// | `code.filepath`        | File of the error's [location][Error::location]    |
+    /// | `code.lineno`          | Line of that location                              |
+    /// | `code.column`          | Column of that location                            |
+    /// | `error.id`             | The error's [ID][Error::id], if it has one         |
+    ///
+    /// The type is that of the error or message that the innermost
+    /// `wallee::Error` was created from, underneath any context. Causes that
+    /// are only reachable through [`source()`][std::error::Error::source]
+    /// have no type that can be named, and neither does a boxed error passed
+    /// to [`Error::from_local`].
+    ///
+    /// There is no `code.function` attribute, as the location recorded by
+    /// wallee does not include the enclosing function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, OtelValue};
+    ///
+    /// let error = wallee!("disk full").context("failed to save draft");
+    /// for (key, value) in error.otel_attributes() {
+    ///     match value {
+    ///         OtelValue::String(value) => println!("{key} = {value:?}"),
+    ///         OtelValue::I64(value) => println!("{key} = {value}"),
+    ///     }
+    /// }
+    /// ```
+    pub fn otel_attributes(&self) -> Vec<(&'static str, OtelValue)> {
+        let origin = unsafe { ErrorImpl::origin(self.inner.as_ref()) };
+        let type_name = unsafe { ErrorImpl::type_names(origin) }.error;
+        let location = self.location();
+        let mut attributes = Vec::new();
+        if let Some(type_name) = type_name {
+            attributes.push(("exception.type", OtelValue::from(type_name)));
+        }
+        attributes.extend([
+            ("exception.message", OtelValue::from(self.to_string())),
+            ("exception.stacktrace", OtelValue::from(format!("{self:?}"))),
+            ("code.filepath", OtelValue::from(location.file())),
+            ("code.lineno", OtelValue::from(location.line())),
+            ("code.column", OtelValue::from(location.column())),
+        ]);
+        if let Some(id) = self.id() {
+            attributes.push(("error.id", OtelValue::from(id.to_string())));
+        }
+        attributes
+    }
+}
+
+impl OtelValue {
+    /// The value if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            OtelValue::String(value) => Some(value),
+            OtelValue::I64(_) => None,
+        }
+    }
+
+    /// The value if it is an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            OtelValue::String(_) => None,
+            OtelValue::I64(value) => Some(*value),
+        }
+    }
+}
+
+impl From<String> for OtelValue {
+    fn from(value: String) -> Self {
+        OtelValue::String(value)
+    }
+}
+
+impl From<&str> for OtelValue {
+    fn from(value: &str) -> Self {
+        OtelValue::String(value.to_string())
+    }
+}
+
+impl From<u32> for OtelValue {
+    fn from(value: u32) -> Self {
+        OtelValue::I64(i64::from(value))
+    }
+}
+
+impl From<i64> for OtelValue {
+    fn from(value: i64) -> Self {
+        OtelValue::I64(value)
+    }
+}
+
+impl Display for OtelValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OtelValue::String(value) => Display::fmt(value, f),
+            OtelValue::I64(value) => Display::fmt(value, f),
+        }
+    }
+}
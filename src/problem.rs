@@ -0,0 +1,266 @@
+use crate::error::ErrorImpl;
+use crate::json;
+use crate::ptr::RefPtr;
+use crate::{Error, HttpStatus, ProblemDetails, ProblemExtension, StdError};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::{self, TypeId};
+use core::fmt::{self, Display};
+
+// Members defined by RFC 7807, which extension members cannot replace.
+const RESERVED: [&str; 5] = ["type", "title", "status", "detail", "instance"];
+
+impl ProblemDetails {
+    /// The media type of the JSON rendering of a problem details object.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// The problem details of an error, leaving out its internal details.
+    pub fn new(error: &Error) -> Self {
+        let status = error.find::<HttpStatus>().map_or(500, |status| status.0);
+        let detail = if (400..500).contains(&status) {
+            status_detail(error)
+        } else {
+            None
+        };
+
+        let mut extensions: Vec<(String, String)> = Vec::new();
+        for extension in error.find_all::<ProblemExtension>() {
+            let name = extension.name();
+            if !RESERVED.contains(&name) && !extensions.iter().any(|(other, _)| other == name) {
+                extensions.push((name.to_owned(), extension.value().to_owned()));
+            }
+        }
+
+        // The ID is what a client can quote to find the error in the logs.
+        if let Some(id) = error.id() {
+            if !extensions.iter().any(|(name, _)| name == "error_id") {
+                extensions.push(("error_id".to_owned(), id.to_string()));
+            }
+        }
+
+        ProblemDetails {
+            type_uri: "about:blank".to_owned(),
+            title: HttpStatus(status)
+                .reason()
+                .unwrap_or("Unknown Status")
+                .to_owned(),
+            status,
+            detail,
+            instance: None,
+            extensions,
+        }
+    }
+
+    /// The problem details of an error, including the whole chain of messages
+    /// and the error's location.
+    ///
+    /// This is meant for development, as it exposes the internals of the
+    /// service to its clients.
+    pub fn verbose(error: &Error) -> Self {
+        let mut problem = ProblemDetails::new(error);
+
+        problem.detail = Some(format!("{:#}", error));
+
+        if !problem
+            .extensions
+            .iter()
+            .any(|(name, _)| name == "location")
+        {
+            let location = error.location();
+            let location = format!(
+                "{}:{}:{}",
+                location.file(),
+                location.line(),
+                location.column(),
+            );
+            problem.extensions.push(("location".to_owned(), location));
+        }
+
+        problem
+    }
+
+    /// Render the problem details as a JSON object.
+    ///
+    /// The standard members come first, leaving out `detail` and `instance`
+    /// if they are `None`, followed by the extension members.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let mut object = json::Object::new(&mut out);
+        object.str("type", &self.type_uri);
+        object.str("title", &self.title);
+        object.u64("status", u64::from(self.status));
+        if let Some(detail) = &self.detail {
+            object.str("detail", detail);
+        }
+        if let Some(instance) = &self.instance {
+            object.str("instance", instance);
+        }
+        for (name, value) in &self.extensions {
+            object.str(name, value);
+        }
+        object.finish();
+        out
+    }
+}
+
+impl From<&Error> for ProblemDetails {
+    fn from(error: &Error) -> Self {
+        ProblemDetails::new(error)
+    }
+}
+
+impl ErrorImpl {
+    // Whether this layer is an HttpStatus or a ProblemExtension attached as
+    // context. These are left out of the chain of messages, as they describe
+    // the response rather than the error.
+    pub(crate) unsafe fn is_attachment(this: RefPtr<Self>) -> bool {
+        let context = unsafe { Self::type_names(this) }.context;
+        context.is_some_and(|context| {
+            context == any::type_name::<HttpStatus>()
+                || context == any::type_name::<ProblemExtension>()
+        })
+    }
+
+    // Whether the chain has any layer that is an attachment.
+    pub(crate) unsafe fn has_attachments(this: RefPtr<Self>) -> bool {
+        let mut layer = Some(this);
+        while let Some(this) = layer {
+            if unsafe { Self::is_attachment(this) } {
+                return true;
+            }
+            layer = unsafe { Self::inner(this) }.map(|inner| inner.inner.as_ref());
+        }
+        false
+    }
+}
+
+// The message right beneath the outermost HttpStatus attached to the error,
+// which is what the status is about. Context attached on top of the status
+// is left out, as it describes what the service was doing.
+fn status_detail(error: &Error) -> Option<String> {
+    let mut layer = Some(error.inner.as_ref());
+    while let Some(this) = layer {
+        let inner = unsafe { ErrorImpl::inner(this) };
+        let status = TypeId::of::<HttpStatus>();
+        if unsafe { ErrorImpl::is_attachment(this) }
+            && unsafe { ErrorImpl::downcast_local(this, status) }.is_some()
+        {
+            return match inner {
+                Some(inner) => Some(inner.to_string()),
+                None => unsafe { ErrorImpl::error(this) }
+                    .source()
+                    .map(ToString::to_string),
+            };
+        }
+        layer = inner.map(|inner| inner.inner.as_ref());
+    }
+    None
+}
+
+impl HttpStatus {
+    /// The reason phrase of the status code, if it is one of those registered
+    /// with IANA.
+    pub fn reason(self) -> Option<&'static str> {
+        Some(match self.0 {
+            100 => "Continue",
+            101 => "Switching Protocols",
+            200 => "OK",
+            201 => "Created",
+            202 => "Accepted",
+            203 => "Non-Authoritative Information",
+            204 => "No Content",
+            205 => "Reset Content",
+            206 => "Partial Content",
+            300 => "Multiple Choices",
+            301 => "Moved Permanently",
+            302 => "Found",
+            303 => "See Other",
+            304 => "Not Modified",
+            307 => "Temporary Redirect",
+            308 => "Permanent Redirect",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            402 => "Payment Required",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            406 => "Not Acceptable",
+            407 => "Proxy Authentication Required",
+            408 => "Request Timeout",
+            409 => "Conflict",
+            410 => "Gone",
+            411 => "Length Required",
+            412 => "Precondition Failed",
+            413 => "Content Too Large",
+            414 => "URI Too Long",
+            415 => "Unsupported Media Type",
+            416 => "Range Not Satisfiable",
+            417 => "Expectation Failed",
+            421 => "Misdirected Request",
+            422 => "Unprocessable Content",
+            423 => "Locked",
+            424 => "Failed Dependency",
+            425 => "Too Early",
+            426 => "Upgrade Required",
+            428 => "Precondition Required",
+            429 => "Too Many Requests",
+            431 => "Request Header Fields Too Large",
+            451 => "Unavailable For Legal Reasons",
+            500 => "Internal Server Error",
+            501 => "Not Implemented",
+            502 => "Bad Gateway",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            505 => "HTTP Version Not Supported",
+            506 => "Variant Also Negotiates",
+            507 => "Insufficient Storage",
+            508 => "Loop Detected",
+            511 => "Network Authentication Required",
+            _ => return None,
+        })
+    }
+}
+
+impl Display for HttpStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+        if let Some(reason) = self.reason() {
+            write!(f, " {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for HttpStatus {}
+
+impl ProblemExtension {
+    /// An extension member with the given name, whose value is rendered with
+    /// `Display`.
+    ///
+    /// The names of the members defined by RFC 7807 are ignored.
+    pub fn new(name: impl Into<String>, value: impl Display) -> Self {
+        ProblemExtension {
+            name: name.into(),
+            value: value.to_string(),
+        }
+    }
+
+    /// The name of the member.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The value of the member.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Display for ProblemExtension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.value)
+    }
+}
+
+impl StdError for ProblemExtension {}
@@ -53,25 +53,25 @@ where
         self.ptr.as_ptr()
     }
 
-    pub fn as_ref(&self) -> RefPtr<T> {
+    pub fn as_ref(&self) -> RefPtr<'_, T> {
         RefPtr {
             ptr: self.ptr,
             lifetime: PhantomData,
         }
     }
 
-    pub fn as_mut(&mut self) -> MutPtr<T> {
+    pub fn as_mut(&mut self) -> MutPtr<'_, T> {
         MutPtr {
             ptr: self.ptr,
             lifetime: PhantomData,
         }
     }
 
-    unsafe pub fn deref<'a>(&self) -> &'a T {
+    pub unsafe fn deref<'a>(&self) -> &'a T {
         unsafe { self.ptr.as_ref() }
     }
 
-    unsafe pub fn deref_mut<'a>(&mut self) -> &'a mut T {
+    pub unsafe fn deref_mut<'a>(&mut self) -> &'a mut T {
         unsafe { self.ptr.as_mut() }
     }
 }
@@ -114,7 +114,7 @@ where
         }
     }
 
-    unsafe pub fn as_ref(self) -> &'a T {
+    pub unsafe fn as_ref(self) -> &'a T {
         unsafe { self.ptr.as_ref() }
     }
 }
@@ -150,7 +150,7 @@ where
         }
     }
 
-    unsafe pub fn as_mut(&mut self) -> &'a mut T {
+    pub unsafe fn as_mut(&mut self) -> &'a mut T {
         unsafe { self.ptr.as_mut() }
     }
 }
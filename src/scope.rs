@@ -0,0 +1,167 @@
+use crate::error::ErrorImpl;
+use crate::location::Location;
+use crate::{Error, InScope, ScopeFrame, ScopeFuture};
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::cell::RefCell;
+
+type Frame = (Arc<dyn Display + Send + Sync>, &'static Location);
+
+thread_local! {
+    static STACK: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+// Pops the frame pushed by `push` when dropped, including on panic.
+struct Guard;
+
+fn push(frame: Frame) -> Guard {
+    STACK.with(|stack| stack.borrow_mut().push(frame));
+    Guard
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let _ = STACK.try_with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+// The frames active on this thread, outermost first.
+pub(crate) fn snapshot() -> Vec<ScopeFrame> {
+    STACK
+        .try_with(|stack| {
+            let stack = stack.borrow();
+            let mut frames = Vec::with_capacity(stack.len());
+            for (message, location) in stack.iter() {
+                frames.push(ScopeFrame {
+                    message: message.to_string(),
+                    location,
+                });
+            }
+            frames
+        })
+        .unwrap_or_default()
+}
+
+/// Run `f` within a scope described by `context`.
+///
+/// Every `wallee::Error` that originates while `f` runs, on this thread, gets
+/// this scope and the scopes around it attached as context, innermost first,
+/// each with the location of its call to `scope`. They show up wherever the
+/// chain of the error does, and are returned by [`Error::scopes`]. This
+/// describes the high-level operation during which an error happened without
+/// attaching the same context at every call site.
+///
+/// For async code, see [`InScope::in_scope`].
+///
+/// # Example
+///
+/// ```
+/// use wallee::{ensure, Result};
+///
+/// fn parse_row(row: &str) -> Result<u32> {
+///     ensure!(!row.is_empty(), "empty row");
+///     Ok(row.parse()?)
+/// }
+///
+/// fn process_batch(id: u32, rows: &[&str]) -> Result<u32> {
+///     wallee::scope(format!("processing batch {}", id), || {
+///         rows.iter().map(|row| parse_row(row)).sum()
+///     })
+/// }
+///
+/// let error = process_batch(17, &["1", ""]).unwrap_err();
+/// assert_eq!(format!("{:#}", error), "processing batch 17: empty row");
+/// assert_eq!(error.scopes()[0].message(), "processing batch 17");
+/// ```
+#[track_caller]
+pub fn scope<C, F, R>(context: C, f: F) -> R
+where
+    C: Display + Send + Sync + 'static,
+    F: FnOnce() -> R,
+{
+    let _guard = push((Arc::new(context), caller!()));
+    f()
+}
+
+impl Error {
+    /// The [scopes][scope()] that were active where this error originated,
+    /// outermost first.
+    ///
+    /// The scopes are attached to the error as context, so these are the
+    /// ones that are still part of its chain.
+    pub fn scopes(&self) -> Vec<&ScopeFrame> {
+        let mut scopes = Vec::new();
+        let mut layer = self;
+        loop {
+            let this = layer.inner.as_ref();
+            if unsafe { ErrorImpl::is_scope(this) } {
+                scopes.extend(layer.downcast_ref::<ScopeFrame>());
+            }
+            match unsafe { ErrorImpl::inner(this) } {
+                Some(inner) => layer = inner,
+                None => return scopes,
+            }
+        }
+    }
+}
+
+impl Display for ScopeFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl ScopeFrame {
+    /// The message describing the scope.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The location of the call that entered the scope.
+    pub fn location(&self) -> &'static Location {
+        self.location
+    }
+}
+
+impl<F> InScope for F
+where
+    F: Future,
+{
+    #[track_caller]
+    fn in_scope<C>(self, context: C) -> ScopeFuture<Self>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        ScopeFuture {
+            future: self,
+            frame: (Arc::new(context), caller!()),
+        }
+    }
+}
+
+impl<F> Future for ScopeFuture<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Safety: the future is never moved out of the pinned ScopeFuture.
+        let this = unsafe { self.get_unchecked_mut() };
+        let _guard = push(this.frame.clone());
+        unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx)
+    }
+}
+
+pub(crate) mod private {
+    use core::future::Future;
+
+    pub trait Sealed {}
+
+    impl<F> Sealed for F where F: Future {}
+}
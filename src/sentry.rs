@@ -0,0 +1,171 @@
+use crate::backtrace::BacktraceStatus;
+use crate::chain::{self, Link};
+use crate::json;
+use crate::Error;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+impl Error {
+    /// Render this error as the JSON payload of a Sentry event.
+    ///
+    /// Every error in the [chain][Error::chain] becomes one of the event's
+    /// `exception.values`, in the same order, so the root cause comes last.
+    /// Each value has the type name of the error if wallee knows it, `Error`
+    /// otherwise, and its message. The top frame of its stack trace is the
+    /// location of the wallee layer that holds it. The value of the root cause
+    /// also gets the frames of the error's backtrace, if one was captured.
+    ///
+    /// The event's fingerprint is the sequence of these locations, so that
+    /// errors are grouped by where they originated and propagated rather than
+    /// by their messages.
+    ///
+    /// The error's [ID][Error::id], if it has one, is the `error_id` tag.
+    ///
+    /// The payload is plain data without an event ID or timestamp, which are
+    /// left to the client that sends it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, Context};
+    ///
+    /// let error = wallee!("disk full").context("failed to save draft");
+    /// let event = error.to_sentry_event();
+    /// assert!(event.starts_with(r#"{"level":"error","#));
+    /// ```
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "sentry")))]
+    pub fn to_sentry_event(&self) -> String {
+        let links = unsafe { chain::links(self.inner.as_ref()) };
+        let backtrace = self.backtrace();
+        let backtrace = match backtrace.status() {
+            BacktraceStatus::Captured => backtrace.to_string(),
+            _ => String::new(),
+        };
+        let backtrace = parse_backtrace(&backtrace);
+
+        let mut out = String::new();
+        let mut event = json::Object::new(&mut out);
+        event.str("level", "error");
+        event.str("platform", "native");
+
+        let mut exception = json::Object::new(event.key("exception"));
+        let mut values = json::Array::new(exception.key("values"));
+        for (i, link) in links.iter().enumerate() {
+            let root = i + 1 == links.len();
+            write_value(values.item(), link, if root { &backtrace } else { &[] });
+        }
+        values.finish();
+        exception.finish();
+
+        let mut fingerprint = json::Array::new(event.key("fingerprint"));
+        let mut previous = None;
+        for link in &links {
+            let location = link.location;
+            if previous != Some(location) {
+                fingerprint.str(&format!("{}:{}", location.file(), location.line()));
+            }
+            previous = Some(location);
+        }
+        fingerprint.finish();
+
+        if let Some(id) = self.id() {
+            let mut tags = json::Object::new(event.key("tags"));
+            tags.str("error_id", &id.to_string());
+            tags.finish();
+        }
+
+        event.finish();
+        out
+    }
+}
+
+// A frame of a captured backtrace, as printed by its Display impl.
+#[derive(Default)]
+struct Frame<'a> {
+    function: Option<&'a str>,
+    filename: Option<&'a str>,
+    lineno: Option<u64>,
+    colno: Option<u64>,
+}
+
+fn write_value(out: &mut String, link: &Link, backtrace: &[Frame]) {
+    let mut value = json::Object::new(out);
+    value.str("type", link.type_name.unwrap_or("Error"));
+    value.str("value", &link.error.to_string());
+
+    let mut stacktrace = json::Object::new(value.key("stacktrace"));
+    let mut frames = json::Array::new(stacktrace.key("frames"));
+    // Sentry lists the frames from the outermost caller to the innermost.
+    for frame in backtrace.iter().rev() {
+        write_frame(frames.item(), frame);
+    }
+    let location = Frame {
+        function: None,
+        filename: Some(link.location.file()),
+        lineno: Some(u64::from(link.location.line())),
+        colno: Some(u64::from(link.location.column())),
+    };
+    write_frame(frames.item(), &location);
+    frames.finish();
+    stacktrace.finish();
+    value.finish();
+}
+
+fn write_frame(out: &mut String, frame: &Frame) {
+    let mut object = json::Object::new(out);
+    if let Some(function) = frame.function {
+        object.str("function", function);
+    }
+    if let Some(filename) = frame.filename {
+        object.str("filename", filename);
+    }
+    if let Some(lineno) = frame.lineno {
+        object.u64("lineno", lineno);
+    }
+    if let Some(colno) = frame.colno {
+        object.u64("colno", colno);
+    }
+    object.finish();
+}
+
+// Parses the frames out of a backtrace printed in the format:
+//
+//    0: wallee::error::<impl wallee::Error>::msg
+//              at ./src/error.rs:72:36
+//    1: ...
+//
+// Frames are listed from the innermost one.
+fn parse_backtrace(backtrace: &str) -> Vec<Frame<'_>> {
+    let mut frames = Vec::new();
+    for line in backtrace.lines() {
+        let line = line.trim_start();
+        if let Some(at) = line.strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut() {
+                parse_file(frame, at);
+            }
+        } else if let Some((index, function)) = line.split_once(": ") {
+            if index.bytes().all(|b| b.is_ascii_digit()) {
+                frames.push(Frame {
+                    function: Some(function),
+                    ..Frame::default()
+                });
+            }
+        }
+    }
+    frames
+}
+
+fn parse_file<'a>(frame: &mut Frame<'a>, at: &'a str) {
+    let mut parts = at.rsplitn(3, ':');
+    let colno = parts.next().and_then(|colno| colno.parse().ok());
+    let lineno = parts.next().and_then(|lineno| lineno.parse().ok());
+    match (parts.next(), lineno, colno) {
+        (Some(filename), Some(lineno), Some(colno)) => {
+            frame.filename = Some(filename);
+            frame.lineno = Some(lineno);
+            frame.colno = Some(colno);
+        }
+        _ => frame.filename = Some(at),
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Error, SharedError, StdError};
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt::{self, Debug, Display};
+use core::ops::Deref;
+
+#[cfg(error_generic_member_access)]
+use std::error::Request;
+
+impl SharedError {
+    /// Convert back into an `Error`.
+    ///
+    /// If this is the last remaining clone, the original `Error` is returned
+    /// as is. Otherwise the result holds on to this `SharedError`, and reports
+    /// the same location, message, cause chain and backtrace as the original.
+    /// Its error and context can be downcast to by reference, but not by
+    /// value or by mutable reference, since the other clones still hold them.
+    #[cold]
+    #[must_use]
+    pub fn into_error(self) -> Error {
+        match Arc::try_unwrap(self.inner) {
+            Ok(error) => error,
+            Err(inner) => Error::from_shared(SharedError { inner }),
+        }
+    }
+
+    /// Returns true if both values are clones of the same shared error.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+}
+
+impl From<Error> for SharedError {
+    #[cold]
+    fn from(error: Error) -> Self {
+        SharedError {
+            inner: Arc::new(error),
+        }
+    }
+}
+
+impl Deref for SharedError {
+    type Target = Error;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl Debug for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+impl StdError for SharedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.inner.source()
+    }
+
+    #[cfg(error_generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        self.inner.provide(request);
+    }
+}
+
+// Recovers a SharedError passed to code that is generic over the error type,
+// so that converting it into an Error does not lose the original location.
+pub(crate) fn try_unshare<E>(error: E) -> Result<SharedError, E>
+where
+    E: 'static,
+{
+    let mut error = Some(error);
+    match (&mut error as &mut dyn Any).downcast_mut::<Option<SharedError>>() {
+        Some(shared) => Ok(shared.take().unwrap()),
+        None => Err(error.unwrap()),
+    }
+}
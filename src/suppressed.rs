@@ -0,0 +1,69 @@
+use crate::error::ErrorImpl;
+use crate::ptr::RefPtr;
+use crate::{Cleanup, Error, Suppressed};
+use core::slice;
+
+#[derive(Clone)]
+pub(crate) struct SuppressedState<'a> {
+    current: slice::Iter<'a, Error>,
+    next: Option<RefPtr<'a, ErrorImpl>>,
+}
+
+impl<'a> Suppressed<'a> {
+    pub(crate) fn new(head: RefPtr<'a, ErrorImpl>) -> Self {
+        Suppressed {
+            state: SuppressedState {
+                current: [].iter(),
+                next: Some(head),
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for Suppressed<'a> {
+    type Item = &'a Error;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = &mut self.state;
+        loop {
+            if let Some(error) = state.current.next() {
+                return Some(error);
+            }
+            let layer = state.next?;
+            state.current = unsafe { ErrorImpl::suppressed(layer) }.iter();
+            state.next = unsafe { ErrorImpl::inner(layer) }.map(|inner| inner.inner.as_ref());
+        }
+    }
+}
+
+impl<T, E> Cleanup<T, E> for Result<T, E>
+where
+    E: Send + Sync + 'static,
+    Error: From<E>,
+{
+    fn or_cleanup<F, C>(self, cleanup: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> Result<(), C>,
+        C: Send + Sync + 'static,
+        Error: From<C>,
+    {
+        match self {
+            Ok(ok) => Ok(ok),
+            Err(error) => {
+                let mut error = Error::from(error);
+                if let Err(cleanup_error) = cleanup() {
+                    error.add_suppressed(Error::from(cleanup_error));
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+pub(crate) mod private {
+    use crate::Error;
+
+    pub trait Sealed {}
+
+    impl<T, E> Sealed for Result<T, E> where Error: From<E> {}
+}
@@ -0,0 +1,70 @@
+use crate::error::ErrorImpl;
+use crate::{Error, TraceErr};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use tracing_error::SpanTrace;
+
+impl Error {
+    /// Get the span trace captured where this error originated.
+    ///
+    /// Spans are only recorded if the subscriber includes
+    /// [`tracing_error::ErrorLayer`], and this returns `None` otherwise. The
+    /// span trace is printed by the `Debug` representation of the error,
+    /// ahead of the backtrace.
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "tracing")))]
+    pub fn span_trace(&self) -> Option<&SpanTrace> {
+        unsafe { ErrorImpl::span_trace(self.inner.as_ref()) }
+    }
+
+    /// Record this error as a `tracing` event at the `ERROR` level.
+    ///
+    /// Besides the message, the event has the following fields:
+    ///
+    /// - `error.chain`: the messages of the error and its causes;
+    /// - `error.file`, `error.line`, `error.column`: where the error
+    ///   originated;
+    /// - `error.id`: the error's [ID][Error::id], if it has one.
+    ///
+    /// The event is recorded within the current span, as with
+    /// `tracing::error!`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wallee::{wallee, Context};
+    ///
+    /// let error = wallee!("disk full").context("failed to save draft");
+    /// error.emit();
+    /// ```
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "tracing")))]
+    pub fn emit(&self) {
+        let chain: Vec<String> = self.chain().map(ToString::to_string).collect();
+        let location = self.location();
+        tracing::error!(
+            error.chain = ?chain,
+            error.file = location.file(),
+            error.line = location.line(),
+            error.column = location.column(),
+            error.id = self.id().map(tracing::field::display),
+            "{}",
+            self,
+        );
+    }
+}
+
+impl<T> TraceErr for Result<T, Error> {
+    fn trace_err(self) -> Self {
+        if let Err(error) = &self {
+            error.emit();
+        }
+        self
+    }
+}
+
+pub(crate) mod private {
+    use crate::Error;
+
+    pub trait Sealed {}
+
+    impl<T> Sealed for Result<T, Error> {}
+}
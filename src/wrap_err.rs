@@ -0,0 +1,85 @@
+use crate::{Error, StdError, WrapErr};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::Display;
+
+pub(crate) mod ext {
+    use super::*;
+
+    pub trait IntoError {
+        #[track_caller]
+        fn into_error(self) -> Error;
+    }
+
+    // Same dispatch as wallee!($err) for each of these types, so that the
+    // boxed error keeps its source chain.
+    macro_rules! into_error {
+        ($($ty:ty),*) => {
+            $(
+                impl IntoError for $ty {
+                    fn into_error(self) -> Error {
+                        #[allow(unused_imports)]
+                        use crate::kind::{BoxedKind, DebugKind};
+                        (&self).__wallee_kind().make(self)
+                    }
+                }
+            )*
+        };
+    }
+
+    into_error! {
+        String,
+        &'static str,
+        Cow<'static, str>,
+        Box<dyn StdError + Send + Sync>
+    }
+
+    impl IntoError for Error {
+        fn into_error(self) -> Error {
+            self
+        }
+    }
+
+    // Not Send, so it cannot be held by a wallee::Error. The messages of its
+    // chain are kept instead.
+    impl IntoError for Box<dyn StdError> {
+        fn into_error(self) -> Error {
+            Error::from_local(self)
+        }
+    }
+}
+
+impl<T, E> WrapErr<T, E> for Result<T, E>
+where
+    E: ext::IntoError,
+{
+    fn wrap_err<C>(self, context: C) -> Result<T, Error>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(ok) => Ok(ok),
+            Err(error) => Err(error.into_error().context(context)),
+        }
+    }
+
+    fn wrap_err_with<C, F>(self, context: F) -> Result<T, Error>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        match self {
+            Ok(ok) => Ok(ok),
+            Err(error) => Err(error.into_error().context(context())),
+        }
+    }
+}
+
+pub(crate) mod private {
+    use super::*;
+
+    pub trait Sealed {}
+
+    impl<T, E> Sealed for Result<T, E> where E: ext::IntoError {}
+}
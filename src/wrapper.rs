@@ -1,4 +1,8 @@
 use crate::StdError;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::{self, Debug, Display};
 
 #[cfg(error_generic_member_access)]
@@ -75,3 +79,114 @@ impl StdError for BoxedError {
         self.0.provide(request);
     }
 }
+
+// An owned copy of the messages and Debug representations of an error that
+// is not Send or Sync, and of its sources.
+pub struct LocalError {
+    message: String,
+    debug: String,
+    source: Option<Box<LocalError>>,
+}
+
+impl LocalError {
+    pub fn new(error: &dyn StdError) -> Self {
+        let mut snapshots = Vec::new();
+        snapshots.push((error.to_string(), format!("{:?}", error)));
+        let mut source = error.source();
+        while let Some(error) = source {
+            snapshots.push((error.to_string(), format!("{:?}", error)));
+            source = error.source();
+        }
+        let mut local = None;
+        for (message, debug) in snapshots.into_iter().rev() {
+            local = Some(LocalError {
+                message,
+                debug,
+                source: local.map(Box::new),
+            });
+        }
+        local.unwrap()
+    }
+}
+
+impl Debug for LocalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.debug)
+    }
+}
+
+impl Display for LocalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl StdError for LocalError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.source {
+            Some(source) => Some(&**source),
+            None => None,
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+#[repr(transparent)]
+pub struct AnyhowError(pub anyhow::Error);
+
+#[cfg(feature = "anyhow")]
+impl Debug for AnyhowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl Display for AnyhowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl StdError for AnyhowError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+
+    #[cfg(error_generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        request.provide_ref::<std::backtrace::Backtrace>(self.0.backtrace());
+        self.0.provide(request);
+    }
+}
+
+#[cfg(feature = "eyre")]
+#[repr(transparent)]
+pub struct EyreError(pub eyre::Report);
+
+#[cfg(feature = "eyre")]
+impl Debug for EyreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl Display for EyreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl StdError for EyreError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+
+    #[cfg(error_generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        self.0.provide(request);
+    }
+}
@@ -1,4 +1,10 @@
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use std::future::Future;
 use std::io;
+use std::pin::pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 use wallee::{bail, Result};
 
 pub fn bail_literal() -> Result<()> {
@@ -12,3 +18,22 @@ pub fn bail_fmt() -> Result<()> {
 pub fn bail_error() -> Result<()> {
     bail!(io::Error::other("oh no!"));
 }
+
+// Polls the future in a loop with a waker that does nothing, which is enough
+// for futures that never wait on anything external.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(std::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
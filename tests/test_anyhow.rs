@@ -0,0 +1,114 @@
+#![cfg(feature = "anyhow")]
+
+use std::io;
+use wallee::{wallee, Error, SharedError};
+
+fn third_party() -> anyhow::Result<()> {
+    let error = io::Error::new(io::ErrorKind::PermissionDenied, "access denied");
+    Err(anyhow::Error::new(error).context("failed to read token"))
+}
+
+fn wallee_error() -> Error {
+    wallee!("disk full").context("failed to save")
+}
+
+// Debug output up to the backtrace, which depends on the environment.
+fn debug<E: std::fmt::Debug>(error: &E) -> String {
+    let debug = format!("{:?}", error);
+    debug
+        .split("\n\nStack backtrace:")
+        .next()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn test_from_anyhow() {
+    let anyhow = third_party().unwrap_err();
+    let expected_display = format!("{:#}", anyhow);
+    let expected_chain: Vec<String> = anyhow.chain().map(ToString::to_string).collect();
+
+    let (error, line) = (Error::from_anyhow(anyhow), line!());
+    assert_eq!(error.line(), line);
+    assert_eq!(error.file(), file!());
+    assert_eq!(format!("{:#}", error), expected_display);
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, expected_chain);
+
+    let expected = format!(
+        "\
+tests/test_anyhow.rs({}:26): failed to read token
+
+Caused by:
+    access denied",
+        line,
+    );
+    assert_eq!(debug(&error), expected);
+
+    assert!(error.downcast_ref::<anyhow::Error>().is_some());
+    assert!(error.find::<io::Error>().is_some());
+}
+
+#[test]
+fn test_macro() {
+    let (error, line) = (wallee!(third_party().unwrap_err()), line!());
+    assert_eq!(error.line(), line);
+    assert_eq!(error.to_string(), "failed to read token");
+}
+
+#[test]
+fn test_into_anyhow() {
+    let error = wallee_error();
+    let expected_display = format!("{:#}", error);
+    let line = error.line();
+
+    let anyhow = anyhow::Error::from(error);
+    assert_eq!(format!("{:#}", anyhow), expected_display);
+    assert_eq!(
+        debug(&anyhow),
+        "\
+failed to save
+
+Caused by:
+    disk full",
+    );
+
+    let shared = anyhow.downcast_ref::<SharedError>().unwrap();
+    assert_eq!(shared.line(), line);
+    assert_eq!(shared.file(), file!());
+}
+
+#[test]
+fn test_question_mark() {
+    fn compat() -> anyhow::Result<()> {
+        Err(wallee_error())?;
+        Ok(())
+    }
+
+    let anyhow = compat().unwrap_err();
+    assert_eq!(anyhow.to_string(), "failed to save");
+    assert!(anyhow.is::<SharedError>());
+}
+
+#[test]
+fn test_round_trip() {
+    let error = wallee_error();
+    let line = error.line();
+    let error = Error::from_anyhow(anyhow::Error::from(error));
+    assert_eq!(error.line(), line);
+    assert_eq!(format!("{:#}", error), "failed to save: disk full");
+    assert!(error.downcast_ref::<SharedError>().is_none());
+
+    let anyhow = third_party().unwrap_err();
+    let anyhow = anyhow::Error::from(Error::from_anyhow(anyhow));
+    assert!(anyhow.downcast_ref::<io::Error>().is_some());
+    assert!(anyhow.downcast_ref::<SharedError>().is_none());
+
+    // Context added on the anyhow side is not lost on the way back.
+    let anyhow = anyhow::Error::from(wallee_error()).context("failed to sync");
+    let error = Error::from_anyhow(anyhow);
+    assert_eq!(
+        format!("{:#}", error),
+        "failed to sync: failed to save: disk full",
+    );
+}
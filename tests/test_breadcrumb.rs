@@ -0,0 +1,85 @@
+use std::io;
+use std::thread;
+use wallee::{bail, breadcrumb, Context, Error, Located, Result};
+
+// The capacity is global, so every test records at most 2 breadcrumbs, which
+// is the capacity set by test_capacity.
+
+fn f() -> Result<()> {
+    bail!("oh no!");
+}
+
+fn messages(error: &Error) -> Vec<&str> {
+    error.breadcrumbs().iter().map(|b| b.message()).collect()
+}
+
+#[test]
+fn test_snapshot() {
+    let path = "config.toml";
+    let line = line!() + 1;
+    breadcrumb!("opened {path}");
+    breadcrumb!("parsed header");
+    let error = f().unwrap_err();
+    assert_eq!(messages(&error), ["opened config.toml", "parsed header"]);
+    let location = error.breadcrumbs()[0].location();
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), line);
+
+    // Breadcrumbs recorded after the error originated are not part of it, even
+    // once context is attached.
+    breadcrumb!("retrying");
+    let error = error.context("context");
+    assert_eq!(messages(&error), ["opened config.toml", "parsed header"]);
+    let (_, error) = error.pop_context().unwrap();
+    assert_eq!(messages(&error), ["opened config.toml", "parsed header"]);
+}
+
+#[test]
+fn test_rebuilt() {
+    breadcrumb!("opened");
+    let located = Located::new(io::Error::other("oh no!"));
+    let context = Err::<(), _>(io::Error::other("oh no!"))
+        .context("context")
+        .unwrap_err();
+
+    // Errors rebuilt after more breadcrumbs were recorded keep the ones from
+    // where the error originated.
+    breadcrumb!("retrying");
+    let error = Error::from(located);
+    assert_eq!(messages(&error), ["opened"]);
+    let (_, error) = context.pop_context().unwrap();
+    assert_eq!(messages(&error), ["opened"]);
+}
+
+#[test]
+fn test_per_thread() {
+    breadcrumb!("main thread");
+    let error = thread::spawn(|| {
+        breadcrumb!("worker thread");
+        f().unwrap_err()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(messages(&error), ["worker thread"]);
+}
+
+#[test]
+fn test_capacity() {
+    wallee::set_breadcrumb_capacity(2);
+    for i in 0..5 {
+        breadcrumb!("step {}", i);
+    }
+    let error = f().unwrap_err();
+    assert_eq!(messages(&error), ["step 3", "step 4"]);
+}
+
+#[test]
+fn test_debug() {
+    let line = line!() + 1;
+    breadcrumb!("connected");
+    let error = f().unwrap_err();
+    let debug = format!("{:?}", error);
+    let breadcrumbs = &debug[debug.find("\n\nBreadcrumbs:").unwrap()..];
+    let expected = format!("\n\nBreadcrumbs:\n    {}({}:5): connected", file!(), line);
+    assert_eq!(&breadcrumbs[..expected.len()], expected);
+}
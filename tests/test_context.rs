@@ -170,3 +170,78 @@ fn test_root_cause() {
 
     assert_eq!(err.root_cause().to_string(), "no such file or directory");
 }
+
+#[test]
+fn test_pop_context() {
+    let (err, dropped) = make_chain();
+
+    let (high, err) = err.pop_context().unwrap();
+    assert_eq!(high.to_string(), "failed to start server");
+    assert!(dropped.none());
+    drop(high);
+    assert!(dropped.high.get());
+
+    let (mid, err) = err.pop_context().unwrap();
+    assert_eq!(mid.to_string(), "failed to load config");
+    assert!(err.is::<LowLevel>());
+    assert!(!err.is::<MidLevel>());
+
+    let err = match err.pop_context() {
+        Ok(_) => panic!("expected no more context"),
+        Err(err) => err,
+    };
+    assert_eq!(err.to_string(), "no such file or directory");
+    assert!(!dropped.low.get());
+
+    drop(mid);
+    drop(err);
+    assert!(dropped.all());
+}
+
+#[test]
+fn test_pop_context_location() {
+    let low = LowLevel {
+        message: "no such file or directory",
+        drop: DetectDrop::new(&Flag::new()),
+    };
+    let (err, line) = (Err::<(), _>(low).context("...").unwrap_err(), line!());
+    let inner = Error::msg("inner");
+    let inner_line = line!() - 1;
+
+    // The std error had no location of its own, so it keeps the context's.
+    let (_, err) = err.pop_context().unwrap();
+    assert_eq!(err.line(), line);
+
+    let (_, err) = inner.context("...").pop_context().unwrap();
+    assert_eq!(err.line(), inner_line);
+}
+
+#[test]
+fn test_map_context() {
+    let (err, dropped) = make_chain();
+    let line = err.line();
+
+    let err = err.map_context(|high| format!("{} (retrying)", high));
+    assert!(dropped.high.get());
+    assert_eq!(err.line(), line);
+    assert_eq!(
+        format!("{:#}", err),
+        "failed to start server (retrying): failed to load config: no such file or directory",
+    );
+
+    let err = Error::msg("no context").map_context(|_| -> &str { unreachable!() });
+    assert_eq!(err.to_string(), "no context");
+}
+
+#[test]
+fn test_into_root() {
+    let (err, dropped) = make_chain();
+
+    let err = err.into_root();
+    assert!(dropped.mid.get() && dropped.high.get());
+    assert_eq!(format!("{:#}", err), "no such file or directory");
+    assert!(err.is::<LowLevel>());
+
+    drop(err);
+    assert!(dropped.all());
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "derive")]
+
+mod common;
+
+use self::common::block_on;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wallee::{bail, ensure, Result};
+
+#[wallee::context("loading user {id}")]
+fn load_user(id: u64) -> Result<String> {
+    ensure!(id != 0, "no such row");
+    Ok(format!("user {}", id))
+}
+
+#[wallee::context("parsing {:?}", input)]
+fn parse(input: String) -> Result<u32> {
+    let digits = input; // consumes the argument
+    Ok(digits.parse()?)
+}
+
+struct Store {
+    name: &'static str,
+}
+
+impl Store {
+    #[wallee::context("reading {key} from {}", self.name)]
+    fn get(&self, key: &str) -> Result<String> {
+        if key.is_empty() {
+            return Err(wallee::wallee!("empty key"));
+        }
+        bail!("not found");
+    }
+}
+
+#[wallee::context("fetching user {id}")]
+async fn fetch_user(id: u64) -> Result<String> {
+    let user = load_user(id)?;
+    Ok(user)
+}
+
+#[test]
+fn test_context() {
+    assert_eq!(load_user(1).unwrap(), "user 1");
+
+    let error = load_user(0).unwrap_err();
+    assert_eq!(format!("{:#}", error), "loading user 0: no such row");
+    assert_eq!(error.line(), 11);
+    assert_eq!(error.file(), file!());
+}
+
+#[test]
+fn test_conversion() {
+    let error = parse("x".to_owned()).unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "parsing \"x\": invalid digit found in string",
+    );
+    assert_eq!(error.line(), 17);
+}
+
+#[test]
+fn test_method() {
+    let store = Store { name: "cache" };
+    let error = store.get("").unwrap_err();
+    assert_eq!(format!("{:#}", error), "reading  from cache: empty key");
+    let error = store.get("port").unwrap_err();
+    assert_eq!(format!("{:#}", error), "reading port from cache: not found");
+    assert_eq!(error.line(), 28);
+}
+
+#[test]
+fn test_async() {
+    assert_eq!(block_on(fetch_user(2)).unwrap(), "user 2");
+
+    let error = block_on(fetch_user(0)).unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "fetching user 0: loading user 0: no such row",
+    );
+    assert_eq!(error.line(), 37);
+}
+
+struct Counters {
+    values: Vec<u32>,
+}
+
+impl Counters {
+    #[wallee::context("getting {i}")]
+    fn get_mut(&mut self, i: usize) -> Result<&mut u32> {
+        let len = self.values.len();
+        match self.values.get_mut(i) {
+            Some(value) => Ok(value),
+            None => bail!("index out of bounds for length {}", len),
+        }
+    }
+}
+
+#[test]
+fn test_borrowed_output() {
+    let mut counters = Counters { values: vec![0, 0] };
+    *counters.get_mut(1).unwrap() += 1;
+    assert_eq!(counters.values, [0, 1]);
+    let error = counters.get_mut(2).unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "getting 2: index out of bounds for length 2",
+    );
+}
+
+static FORMATTED: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+
+impl Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        FORMATTED.fetch_add(1, Ordering::Relaxed);
+        f.write_str("counted")
+    }
+}
+
+#[wallee::context("checking {}", Counted)]
+fn check(ok: bool) -> Result<()> {
+    ensure!(ok, "check failed");
+    Ok(())
+}
+
+#[test]
+fn test_lazy() {
+    check(true).unwrap();
+    assert_eq!(FORMATTED.load(Ordering::Relaxed), 0);
+    let error = check(false).unwrap_err();
+    assert_eq!(FORMATTED.load(Ordering::Relaxed), 1);
+    assert_eq!(format!("{:#}", error), "checking counted: check failed");
+}
+
+#[wallee::context("listing evens below {limit}")]
+fn evens(limit: u32) -> Result<impl Iterator<Item = u32>> {
+    ensure!(limit > 0, "empty range");
+    Ok((0..limit).filter(|n| n % 2 == 0))
+}
+
+#[test]
+fn test_impl_trait() {
+    assert_eq!(evens(5).unwrap().collect::<Vec<_>>(), [0, 2, 4]);
+    let error = evens(0).err().unwrap();
+    assert_eq!(format!("{:#}", error), "listing evens below 0: empty range",);
+}
+
+#[wallee::context("tracked")]
+#[track_caller]
+fn tracked() -> Result<()> {
+    bail!("oh no!");
+}
+
+#[test]
+fn test_track_caller() {
+    let error = tracked().unwrap_err();
+    assert_eq!(error.line(), 152);
+    assert_eq!(error.file(), file!());
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "derive")]
+
+use std::fmt::Display;
+use std::io;
+use thiserror::Error;
+use wallee::{wallee, Error, HasLocation, Locate, Location, Result};
+
+#[derive(Error, Debug, Locate)]
+#[locate(constructors)]
+enum ConfigError {
+    #[error("missing key {key:?}")]
+    MissingKey {
+        key: String,
+        #[location]
+        location: &'static Location,
+    },
+    #[error("failed to read config")]
+    Read(
+        #[source]
+        #[locate(from)]
+        io::Error,
+        #[location] &'static Location,
+    ),
+    #[error("HTTP timeout")]
+    HTTPTimeout(#[location] &'static Location),
+    #[error("bad type")]
+    Type {
+        #[location]
+        location: &'static Location,
+    },
+}
+
+#[derive(Error, Debug, Locate)]
+#[locate(constructors)]
+#[error("invalid value {value}")]
+struct InvalidValue<T: Display> {
+    value: T,
+    #[location]
+    at: &'static Location,
+}
+
+// Without #[locate(constructors)], the type is free to define its own `new`,
+// and a field named `location` is not the location unless marked so.
+#[derive(Error, Debug, Locate)]
+#[error("failed to reach {location}")]
+struct Unreachable {
+    location: String,
+    #[location]
+    at: &'static Location,
+}
+
+impl Unreachable {
+    #[track_caller]
+    fn new(location: &str) -> Self {
+        Unreachable {
+            location: location.to_owned(),
+            at: Location::caller(),
+        }
+    }
+}
+
+fn read() -> Result<(), ConfigError> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "no such file"))?;
+    Ok(())
+}
+
+#[test]
+fn test_constructors() {
+    let (error, line) = (ConfigError::missing_key("port".to_owned()), line!());
+    assert_eq!(error.location().line(), line);
+    assert_eq!(error.location().file(), file!());
+    assert_eq!(error.to_string(), "missing key \"port\"");
+
+    let (error, line) = (ConfigError::http_timeout(), line!());
+    assert_eq!(error.location().line(), line);
+    assert!(matches!(ConfigError::r#type(), ConfigError::Type { .. }));
+
+    let (error, line) = (InvalidValue::new(-1), line!());
+    assert_eq!(error.location().line(), line);
+    assert_eq!(error.to_string(), "invalid value -1");
+}
+
+#[test]
+fn test_from() {
+    let error = read().unwrap_err();
+    assert_eq!(error.location().line(), 63);
+    assert!(matches!(error, ConfigError::Read(..)));
+}
+
+#[test]
+fn test_into_error() {
+    let (config_error, line) = (ConfigError::missing_key("port".to_owned()), line!());
+    let error = wallee!(config_error);
+    assert_eq!(error.line(), line);
+    assert!(error.is::<ConfigError>());
+
+    let (config_error, line) = (ConfigError::missing_key("port".to_owned()), line!());
+    let error = Error::from_located(config_error);
+    assert_eq!(error.line(), line);
+
+    // Error::new and `?` record the location of the conversion, whatever ran
+    // before.
+    let config_error = ConfigError::Type {
+        location: Location::caller(),
+    };
+    let (error, line) = (Error::new(config_error), line!());
+    assert_eq!(error.line(), line);
+
+    fn load() -> Result<()> {
+        read()?;
+        Ok(())
+    }
+    let error = load().unwrap_err();
+    assert_eq!(error.line(), 110);
+    assert_eq!(error.file(), file!());
+
+    fn load_located() -> Result<()> {
+        read().map_err(Error::from_located)?;
+        Ok(())
+    }
+    let error = load_located().unwrap_err();
+    assert_eq!(error.line(), 63);
+    assert!(error.is::<ConfigError>());
+}
+
+#[test]
+fn test_own_constructor() {
+    let (error, line) = (Unreachable::new("db"), line!());
+    assert_eq!(error.location().line(), line);
+    assert_eq!(error.to_string(), "failed to reach db");
+
+    let error = wallee!(error);
+    assert_eq!(error.line(), line);
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "eyre")]
+
+use eyre::EyreHandler;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::panic::Location;
+use std::sync::Once;
+use wallee::{wallee, Error, SharedError};
+
+// Renders "{chain} at {location}" followed by any note, standing in for a
+// handler such as color-eyre's.
+struct Handler {
+    location: Option<&'static Location<'static>>,
+    note: Option<String>,
+}
+
+impl EyreHandler for Handler {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", error)?;
+        let mut source = error.source();
+        while let Some(error) = source {
+            write!(f, " <- {}", error)?;
+            source = error.source();
+        }
+        if let Some(location) = self.location {
+            write!(f, " at {}:{}", location.file(), location.line())?;
+        }
+        if let Some(note) = &self.note {
+            write!(f, "\nNote: {}", note)?;
+        }
+        Ok(())
+    }
+
+    fn track_caller(&mut self, location: &'static Location<'static>) {
+        self.location = Some(location);
+    }
+}
+
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        eyre::set_hook(Box::new(|_| {
+            Box::new(Handler {
+                location: None,
+                note: None,
+            })
+        }))
+        .unwrap();
+    });
+}
+
+fn note(report: &mut eyre::Report, note: &str) {
+    let handler = report.handler_mut().downcast_mut::<Handler>().unwrap();
+    handler.note = Some(note.to_owned());
+}
+
+fn third_party() -> eyre::Result<()> {
+    let error = io::Error::new(io::ErrorKind::PermissionDenied, "access denied");
+    Err(eyre::Report::new(error).wrap_err("failed to read token"))
+}
+
+fn wallee_error() -> Error {
+    wallee!("disk full").context("failed to save")
+}
+
+#[test]
+fn test_from_eyre() {
+    install();
+    let report = third_party().unwrap_err();
+    let expected_display = format!("{:#}", report);
+    let expected_chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+
+    let (error, line) = (Error::from_eyre(report), line!());
+    assert_eq!(error.line(), line);
+    assert_eq!(error.file(), file!());
+    assert_eq!(format!("{:#}", error), expected_display);
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, expected_chain);
+
+    assert!(error.downcast_ref::<eyre::Report>().is_some());
+    assert!(error.find::<io::Error>().is_some());
+
+    let (error, line) = (wallee!(third_party().unwrap_err()), line!());
+    assert_eq!(error.line(), line);
+}
+
+#[test]
+fn test_into_eyre() {
+    install();
+    let error = wallee_error();
+    let line = error.line();
+
+    let report = eyre::Report::from(error);
+    assert_eq!(
+        format!("{:?}", report),
+        format!("failed to save <- disk full at tests/test_eyre.rs:{}", line),
+    );
+    let shared = report.downcast_ref::<SharedError>().unwrap();
+    assert_eq!(shared.line(), line);
+}
+
+#[test]
+fn test_round_trip() {
+    install();
+    let mut report = third_party().unwrap_err();
+    note(&mut report, "check the token file permissions");
+    let expected = format!("{:?}", report);
+
+    // The report and its handler come back unchanged.
+    let report = eyre::Report::from(Error::from_eyre(report));
+    assert_eq!(format!("{:?}", report), expected);
+
+    let error = wallee_error();
+    let line = error.line();
+    let error = Error::from_eyre(eyre::Report::from(error));
+    assert_eq!(error.line(), line);
+    assert!(error.downcast_ref::<SharedError>().is_none());
+}
+
+#[test]
+fn test_eyre_report() {
+    install();
+    let error = wallee_error();
+    assert_eq!(
+        format!("{:?}", error.eyre_report()),
+        format!(
+            "failed to save <- disk full at tests/test_eyre.rs:{}",
+            error.line(),
+        ),
+    );
+    assert_eq!(
+        format!("{:#}", error.eyre_report()),
+        "failed to save: disk full"
+    );
+}
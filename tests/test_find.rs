@@ -0,0 +1,124 @@
+use std::fmt;
+use std::io;
+use thiserror::Error;
+use wallee::{wallee, Context, Error, Result};
+
+#[derive(Error, Debug)]
+enum ConfigError {
+    #[error("failed to read config")]
+    Read(#[source] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("failed to start server on port {port}")]
+struct StartError {
+    port: u16,
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "no such file")
+}
+
+fn read_config() -> Result<(), ConfigError> {
+    Err(ConfigError::Read(io_error()))
+}
+
+fn load() -> Result<()> {
+    read_config().context("failed to load config")
+}
+
+fn start() -> Result<()> {
+    load().context(StartError { port: 80 })
+}
+
+#[test]
+fn test_find_through_source() {
+    let error = start().unwrap_err();
+    assert!(error.downcast_ref::<io::Error>().is_none());
+
+    let found = error.find::<io::Error>().unwrap();
+    assert_eq!(found.kind(), io::ErrorKind::NotFound);
+    assert_eq!(found.location().line(), 27);
+    assert!(error.contains::<io::Error>());
+}
+
+#[test]
+fn test_find_context() {
+    let error = start().unwrap_err();
+
+    let found = error.find::<StartError>().unwrap();
+    assert_eq!(found.port, 80);
+    assert_eq!(found.location().line(), 31);
+
+    let found = error.find::<ConfigError>().unwrap();
+    assert_eq!(found.location().line(), 27);
+    assert_eq!(found.location().file(), file!());
+
+    assert!(!error.contains::<fmt::Error>());
+}
+
+#[test]
+fn test_find_boxed() {
+    let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(ConfigError::Read(io_error()));
+    let error = wallee!(boxed).context("failed to load config");
+
+    assert!(error.contains::<ConfigError>());
+    assert!(error.contains::<io::Error>());
+}
+
+#[test]
+fn test_find_all() {
+    let error = Error::new(io::Error::other(ConfigError::Read(io_error())));
+    let kinds: Vec<io::ErrorKind> = error
+        .find_all::<io::Error>()
+        .into_iter()
+        .map(|found| found.kind())
+        .collect();
+    assert_eq!(kinds, [io::ErrorKind::Other, io::ErrorKind::NotFound]);
+
+    let error = Error::new(io_error()).context(StartError { port: 1 });
+    let error = Err::<(), _>(error)
+        .context(StartError { port: 2 })
+        .unwrap_err();
+    let ports: Vec<u16> = error
+        .find_all::<StartError>()
+        .into_iter()
+        .map(|found| found.port)
+        .collect();
+    assert_eq!(ports, [2, 1]);
+    assert_eq!(error.find_all::<io::Error>().len(), 1);
+    assert!(error.find_all::<fmt::Error>().is_empty());
+}
+
+#[test]
+fn test_find_mut() {
+    let mut error = start().unwrap_err();
+    let mut found = error.find_mut::<StartError>().unwrap();
+    assert_eq!(found.location().line(), 31);
+    found.port = 8080;
+    assert_eq!(error.to_string(), "failed to start server on port 8080");
+
+    // Only reachable through source(), which cannot be borrowed mutably.
+    assert!(error.find_mut::<io::Error>().is_none());
+}
+
+#[test]
+fn test_find_plain_context() {
+    #[derive(Debug)]
+    struct Request {
+        id: u32,
+    }
+
+    impl fmt::Display for Request {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "while handling request {}", self.id)
+        }
+    }
+
+    let mut error = load().context(Request { id: 7 }).unwrap_err();
+    let mut found = error.find_mut::<Request>().unwrap();
+    assert_eq!(found.location().line(), 118);
+    found.id = 8;
+    assert_eq!(error.to_string(), "while handling request 8");
+    assert_eq!(error.downcast_ref::<Request>().unwrap().id, 8);
+}
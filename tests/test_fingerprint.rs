@@ -0,0 +1,76 @@
+use std::io;
+use std::time::Duration;
+use wallee::{bail, wallee, Context, Dedup, Error, Result};
+
+fn open(path: &str) -> Result<()> {
+    bail!("no such file: {}", path);
+}
+
+fn load(path: &str) -> Result<()> {
+    open(path).with_context(|| format!("failed to load {}", path))
+}
+
+#[test]
+fn test_ignores_messages() {
+    let a = load("a.toml").unwrap_err();
+    let b = load("b.toml").unwrap_err();
+    assert_ne!(a.to_string(), b.to_string());
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert_eq!(a.fingerprint_hex(), b.fingerprint_hex());
+}
+
+#[test]
+fn test_locations() {
+    let loaded = load("a.toml").unwrap_err();
+    let opened = open("a.toml").unwrap_err();
+    let other = wallee!("no such file: a.toml");
+    assert_ne!(loaded.fingerprint(), opened.fingerprint());
+    assert_ne!(opened.fingerprint(), other.fingerprint());
+}
+
+// Creates every error at the same location.
+fn new_error<E>(error: E) -> Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    Error::new(error)
+}
+
+#[test]
+fn test_types() {
+    let io_error = new_error(io::Error::other("oh no!"));
+    let fmt_error = new_error(std::fmt::Error);
+    assert_eq!(io_error.location(), fmt_error.location());
+    assert_ne!(io_error.fingerprint(), fmt_error.fingerprint());
+}
+
+#[test]
+fn test_hex() {
+    let error = open("a.toml").unwrap_err();
+    let hex = error.fingerprint_hex();
+    assert_eq!(hex.len(), 16);
+    assert_eq!(u64::from_str_radix(&hex, 16).unwrap(), error.fingerprint());
+}
+
+#[test]
+fn test_dedup() {
+    let dedup = Dedup::new(Duration::from_secs(3600));
+    let reported: Vec<Option<u64>> = (0..5)
+        .map(|i| dedup.check(&open(&i.to_string()).unwrap_err()))
+        .collect();
+    assert_eq!(reported, [Some(0), None, None, None, None]);
+    assert_eq!(dedup.count(&open("a.toml").unwrap_err()), 5);
+
+    // A different kind of error is reported on its own.
+    let error = load("a.toml").unwrap_err();
+    assert_eq!(dedup.check(&error), Some(0));
+    assert_eq!(dedup.count(&error), 1);
+}
+
+#[test]
+fn test_dedup_interval() {
+    let dedup = Dedup::new(Duration::ZERO);
+    for _ in 0..3 {
+        assert_eq!(dedup.check(&open("a.toml").unwrap_err()), Some(0));
+    }
+}
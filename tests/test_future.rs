@@ -0,0 +1,70 @@
+#![cfg(feature = "futures")]
+
+mod common;
+
+use self::common::block_on;
+use futures::future;
+use futures::stream::{self, StreamExt as _};
+use std::io;
+use wallee::{bail, FutureExt, Result, TryStreamExt};
+
+async fn f() -> Result<()> {
+    bail!("oh no!");
+}
+
+#[test]
+fn test_context() {
+    let line = line!() + 1;
+    let future = f().context("context");
+    let error = block_on(future).unwrap_err();
+    assert_eq!(error.to_string(), "context");
+    assert_eq!(error.root_cause().to_string(), "oh no!");
+    assert_eq!(error.file(), file!());
+    assert_eq!(error.line(), line);
+    assert_eq!(error.column(), 22);
+
+    let ok = block_on(future::ready(Ok::<_, io::Error>(1)).context("context"));
+    assert_eq!(ok.unwrap(), 1);
+}
+
+#[test]
+fn test_with_context() {
+    let mut called = false;
+    let ok = block_on(future::ready(Ok::<_, io::Error>(1)).with_context(|| {
+        called = true;
+        "context"
+    }));
+    assert_eq!(ok.unwrap(), 1);
+    assert!(!called);
+
+    let future = future::ready(Err::<(), _>(io::Error::other("oh no!")));
+    let error = block_on(future.with_context(|| format!("context {}", 1))).unwrap_err();
+    assert_eq!(error.to_string(), "context 1");
+    assert!(error.root_cause().is::<io::Error>());
+}
+
+#[test]
+fn test_stream() {
+    let line = line!() + 2;
+    let rows = stream::iter(vec![Ok(1), Err(io::Error::other("truncated")), Ok(3)])
+        .context("failed to read rows");
+    let rows: Vec<Result<u32>> = block_on(rows.collect());
+    assert_eq!(rows.len(), 3);
+    assert_eq!(*rows[0].as_ref().unwrap(), 1);
+    let error = rows[1].as_ref().unwrap_err();
+    assert_eq!(error.to_string(), "failed to read rows");
+    assert_eq!(error.line(), line);
+
+    let mut n = 0;
+    let rows = stream::iter(vec![Err(io::Error::other("a")), Err(io::Error::other("b"))])
+        .with_context(|| {
+            n += 1;
+            format!("row {}", n)
+        });
+    let rows: Vec<Result<u32>> = block_on(rows.collect());
+    let messages: Vec<String> = rows
+        .iter()
+        .map(|row| row.as_ref().unwrap_err().to_string())
+        .collect();
+    assert_eq!(messages, ["row 1", "row 2"]);
+}
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+use std::io;
+use wallee::{bail, Context, Error, ProblemDetails, Result};
+
+fn f() -> Result<()> {
+    bail!(io::Error::new(io::ErrorKind::PermissionDenied, "oh no!"));
+}
+
+fn g() -> Result<()> {
+    f().context("f failed")
+}
+
+fn error() -> Error {
+    wallee::set_error_ids(true);
+    g().unwrap_err()
+}
+
+#[test]
+fn test_format() {
+    let id = error().id().unwrap().to_string();
+    assert_eq!(id.len(), 10);
+    assert!(id.starts_with("err-"));
+    assert!(id[4..]
+        .bytes()
+        .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')));
+}
+
+#[test]
+fn test_unique() {
+    let ids: HashSet<_> = (0..10_000).map(|_| error().id().unwrap()).collect();
+    assert_eq!(ids.len(), 10_000);
+}
+
+#[test]
+fn test_context() {
+    let error = error();
+    let id = error.id();
+    assert!(id.is_some());
+
+    let error = error.context("g failed");
+    assert_eq!(error.id(), id);
+
+    let (_, error) = error.pop_context().unwrap();
+    let (_, error) = error.pop_context().unwrap();
+    assert_eq!(error.to_string(), "oh no!");
+    assert_eq!(error.id(), id);
+
+    let shared = error.share();
+    assert_eq!(Error::new(shared.clone()).context("context").id(), id);
+    assert_eq!(shared.into_error().id(), id);
+}
+
+#[test]
+fn test_std_context() {
+    wallee::set_error_ids(true);
+    let error = Err::<(), _>(io::Error::other("oh no!"))
+        .context("context")
+        .unwrap_err();
+    let id = error.id();
+    assert!(id.is_some());
+    let (_, error) = error.pop_context().unwrap();
+    assert_eq!(error.id(), id);
+}
+
+#[test]
+fn test_debug() {
+    let error = error();
+    let id = error.id().unwrap();
+    let expected = format!(
+        "\
+[{}] tests/test_id.rs(10:9): f failed
+
+Caused by:
+    oh no!",
+        id,
+    );
+    assert_eq!(expected, format!("{:?}", error)[..expected.len()]);
+
+    let debug = format!("{:#?}", error);
+    assert_eq!(debug.matches(&id.to_string()).count(), 1);
+}
+
+#[test]
+fn test_structured() {
+    let error = error();
+    let id = error.id().unwrap().to_string();
+
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(problem.extensions, [("error_id".to_owned(), id.clone())]);
+
+    let attributes = error.otel_attributes();
+    let (key, value) = attributes.last().unwrap();
+    assert_eq!(*key, "error.id");
+    assert_eq!(value.as_str(), Some(id.as_str()));
+}
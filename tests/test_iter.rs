@@ -0,0 +1,66 @@
+use std::num::ParseIntError;
+use wallee::{IterContext, Result};
+
+const ROWS: [&str; 5] = ["1", "x", "3", "y", "5"];
+
+fn parse(row: &&str) -> Result<u32, ParseIntError> {
+    row.parse()
+}
+
+#[test]
+fn test_context_each() {
+    let line = line!() + 4;
+    let results: Vec<Result<u32>> = ROWS
+        .iter()
+        .map(parse)
+        .context_each(|i| format!("row {}", i))
+        .collect();
+    assert_eq!(results.len(), 5);
+    assert_eq!(*results[0].as_ref().unwrap(), 1);
+    let error = results[1].as_ref().unwrap_err();
+    assert_eq!(error.to_string(), "row 1");
+    assert!(error.root_cause().is::<ParseIntError>());
+    assert_eq!(error.line(), line);
+    assert_eq!(results[3].as_ref().unwrap_err().to_string(), "row 3");
+}
+
+#[test]
+fn test_try_collect_context() {
+    let mut calls = 0;
+    let error = ROWS
+        .iter()
+        .map(parse)
+        .try_collect_context::<Vec<_>, _, _>(|i| {
+            calls += 1;
+            format!("row {} ({:?})", i, ROWS[i])
+        })
+        .unwrap_err();
+    assert_eq!(error.to_string(), r#"row 1 ("x")"#);
+    assert_eq!(calls, 1);
+
+    let ok: Vec<u32> = ["1", "2"]
+        .iter()
+        .map(parse)
+        .try_collect_context(|i| format!("row {}", i))
+        .unwrap();
+    assert_eq!(ok, [1, 2]);
+}
+
+#[test]
+fn test_collect_all_context() {
+    let error = ROWS
+        .iter()
+        .map(parse)
+        .collect_all_context::<Vec<_>, _, _>(|i| format!("row {}", i))
+        .unwrap_err();
+    assert_eq!(error.to_string(), "row 1");
+    let suppressed: Vec<String> = error.suppressed().map(|e| e.to_string()).collect();
+    assert_eq!(suppressed, ["row 3"]);
+
+    let ok: Vec<u32> = ["1", "2"]
+        .iter()
+        .map(parse)
+        .collect_all_context(|i| format!("row {}", i))
+        .unwrap();
+    assert_eq!(ok, [1, 2]);
+}
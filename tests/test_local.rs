@@ -0,0 +1,67 @@
+use std::any;
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::io;
+use std::rc::Rc;
+use wallee::Error;
+
+#[derive(Debug)]
+struct LocalError {
+    path: Rc<str>,
+    source: io::Error,
+}
+
+impl Display for LocalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to open {}", self.path)
+    }
+}
+
+impl StdError for LocalError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn local_error() -> LocalError {
+    LocalError {
+        path: Rc::from("db.bin"),
+        source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+    }
+}
+
+#[test]
+fn test_snapshot() {
+    let line = line!() + 1;
+    let error = Error::from_local(local_error());
+    assert_eq!(error.line(), line);
+    assert_eq!(format!("{:#}", error), "failed to open db.bin: not found");
+    assert_eq!(error.type_name(), Some(any::type_name::<LocalError>()));
+
+    let debug = format!("{:?}", local_error());
+    assert_eq!(format!("{:?}", error.chain().next().unwrap()), debug);
+    let debug = format!("{:?}", local_error().source);
+    assert_eq!(format!("{:?}", error.root_cause()), debug);
+
+    // The original errors are gone, only their snapshots are left.
+    assert!(!error.root_cause().is::<io::Error>());
+}
+
+#[test]
+fn test_boxed() {
+    let boxed: Box<dyn StdError> = Box::new(local_error());
+    let error = Error::from_local(boxed);
+    assert_eq!(error.type_name(), None);
+    assert_eq!(error.typed_chain().next().unwrap().0, None);
+
+    let error = error.context("context");
+    assert_eq!(
+        format!("{:#}", error),
+        "context: failed to open db.bin: not found",
+    );
+    assert_eq!(error.chain().count(), 3);
+
+    let error = Error::from_local("oh no!");
+    assert_eq!(error.to_string(), "oh no!");
+    assert_eq!(error.type_name(), Some("&str"));
+}
@@ -0,0 +1,77 @@
+use std::io;
+use thiserror::Error;
+use wallee::{wallee, Context, Error, Located, Result};
+
+#[derive(Error, Debug, PartialEq)]
+enum ParseError {
+    #[error("unexpected end of input")]
+    Eof,
+    #[error("invalid digit {0:?}")]
+    InvalidDigit(char),
+}
+
+fn parse_digit(input: &str) -> Result<u32, Located<ParseError>> {
+    let c = input.chars().next().ok_or(ParseError::Eof)?;
+    let digit = c.to_digit(10).ok_or(ParseError::InvalidDigit(c))?;
+    Ok(digit)
+}
+
+fn parse(input: &str) -> Result<u32> {
+    let digit = parse_digit(input)?;
+    Ok(digit)
+}
+
+#[test]
+fn test_question_mark() {
+    let error = parse_digit("").unwrap_err();
+    assert_eq!(*error, ParseError::Eof);
+    assert_eq!(error.location().line(), 14);
+    assert_eq!(error.location().file(), file!());
+
+    let error = parse_digit("x").unwrap_err();
+    assert!(matches!(*error, ParseError::InvalidDigit('x')));
+    assert_eq!(error.location().line(), 15);
+    assert_eq!(error.to_string(), "invalid digit 'x'");
+    assert_eq!(error.into_inner(), ParseError::InvalidDigit('x'));
+}
+
+#[test]
+fn test_into_error() {
+    let error = parse("x").unwrap_err();
+    assert_eq!(error.line(), 15);
+    assert_eq!(error.file(), file!());
+    assert_eq!(
+        error.downcast_ref::<ParseError>(),
+        Some(&ParseError::InvalidDigit('x')),
+    );
+
+    let (located, line) = (Located::new(io::Error::other("oh no")), line!());
+    let error = Error::from(located);
+    assert_eq!(error.line(), line);
+
+    let (located, line) = (Located::from(io::Error::other("oh no")), line!());
+    let error = wallee!(located);
+    assert_eq!(error.line(), line);
+}
+
+#[test]
+fn test_context() {
+    let (result, line) = (parse_digit("").context("failed to parse"), line!());
+    let error = result.unwrap_err();
+    assert_eq!(error.line(), line);
+    assert_eq!(
+        format!("{:#}", error),
+        "failed to parse: unexpected end of input"
+    );
+
+    let found = error.find::<ParseError>().unwrap();
+    assert_eq!(found.location().line(), 14);
+}
+
+#[test]
+fn test_deref_mut() {
+    let mut error = Located::new(io::Error::other("oh no"));
+    *error = io::Error::new(io::ErrorKind::NotFound, "missing");
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    assert!(error.backtrace().is_some());
+}
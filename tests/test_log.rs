@@ -0,0 +1,124 @@
+#![cfg(feature = "log")]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::RefCell;
+use std::sync::Once;
+use std::thread;
+use std::time::Duration;
+use wallee::{bail, log_error, Context, Dedup, LogErr, Result};
+
+#[derive(Debug, PartialEq)]
+struct Logged {
+    level: Level,
+    target: String,
+    file: Option<String>,
+    line: Option<u32>,
+    message: String,
+}
+
+thread_local! {
+    static LOGGED: RefCell<Vec<Logged>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Logger;
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() != "quiet"
+    }
+
+    fn log(&self, record: &Record) {
+        let logged = Logged {
+            level: record.level(),
+            target: record.target().to_owned(),
+            file: record.file().map(str::to_owned),
+            line: record.line(),
+            message: record.args().to_string(),
+        };
+        LOGGED.with(|records| records.borrow_mut().push(logged));
+    }
+
+    fn flush(&self) {}
+}
+
+fn logged() -> Vec<Logged> {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        log::set_logger(&Logger).unwrap();
+        log::set_max_level(LevelFilter::Info);
+    });
+    LOGGED.with(|records| records.borrow_mut().drain(..).collect())
+}
+
+fn write_rows() -> Result<()> {
+    bail!("disk full");
+}
+
+#[test]
+fn test_log() {
+    logged();
+    let (error, line) = (write_rows().context("failed to commit"), line!());
+    error.unwrap_err().log(Level::Warn);
+
+    assert_eq!(
+        logged(),
+        [Logged {
+            level: Level::Warn,
+            target: "wallee".to_owned(),
+            file: Some(file!().to_owned()),
+            line: Some(line),
+            message: "failed to commit: disk full".to_owned(),
+        }],
+    );
+}
+
+#[test]
+fn test_log_error() {
+    logged();
+    let error = write_rows().unwrap_err();
+    log_error!(error, Level::Warn);
+    error.log_to(Level::Warn, "quiet");
+
+    let logged = logged();
+    assert_eq!(logged.len(), 1);
+    assert_eq!(logged[0].target, module_path!());
+    assert_eq!(logged[0].message, "disk full");
+}
+
+#[test]
+fn test_max_level() {
+    logged();
+    write_rows().unwrap_err().log(Level::Debug);
+    assert!(logged().is_empty());
+}
+
+#[test]
+fn test_log_err() {
+    logged();
+    assert_eq!(Ok::<_, wallee::Error>(1).log_err(), Some(1));
+    assert!(logged().is_empty());
+
+    assert_eq!(write_rows().log_err(), None);
+    let logged = logged();
+    assert_eq!(logged.len(), 1);
+    assert_eq!(logged[0].level, Level::Error);
+    assert_eq!(logged[0].line, Some(54));
+    assert_eq!(logged[0].message, "disk full");
+}
+
+#[test]
+fn test_dedup() {
+    logged();
+    let dedup = Dedup::new(Duration::from_millis(50));
+    for _ in 0..10 {
+        dedup.log(&write_rows().unwrap_err(), Level::Warn);
+    }
+    thread::sleep(Duration::from_millis(100));
+    dedup.log(&write_rows().unwrap_err(), Level::Warn);
+
+    let messages: Vec<String> = logged().into_iter().map(|logged| logged.message).collect();
+    assert_eq!(
+        messages,
+        ["disk full", "disk full (9 identical errors suppressed)"],
+    );
+}
@@ -0,0 +1,95 @@
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wallee::{bail, Error, Result};
+
+fn f() -> Result<()> {
+    bail!("oh no!");
+}
+
+fn capture() {
+    wallee::set_thread_capture(true);
+    wallee::set_time_capture(true);
+}
+
+fn on_worker() -> Error {
+    thread::Builder::new()
+        .name("worker-1".to_owned())
+        .spawn(|| {
+            capture();
+            f().unwrap_err()
+        })
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+#[test]
+fn test_thread() {
+    let error = on_worker();
+    let thread = error.thread().unwrap();
+    assert_eq!(thread.name(), Some("worker-1"));
+    assert_ne!(thread.id(), thread::current().id());
+
+    // Context attached on another thread keeps the original thread.
+    let error = error.context("context");
+    assert_eq!(error.thread().unwrap().name(), Some("worker-1"));
+    let (_, error) = error.pop_context().unwrap();
+    assert_eq!(error.thread().unwrap().name(), Some("worker-1"));
+}
+
+#[test]
+fn test_created_at() {
+    capture();
+    let before = SystemTime::now();
+    let error = f().unwrap_err();
+    let after = SystemTime::now();
+    let created_at = error.created_at().unwrap();
+    assert!(before <= created_at && created_at <= after);
+    assert_eq!(error.context("context").created_at(), Some(created_at));
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[test]
+fn test_debug() {
+    let (error, line) = (on_worker().context("context"), line!());
+    let debug = format!("{:?}", error);
+    let mut lines = debug.lines();
+    let expected = format!("tests/test_origin.rs({}:38): context", line);
+    assert_eq!(lines.next(), Some(expected.as_str()));
+
+    let thread = error.thread().unwrap();
+    let expected = format!("Thread: 'worker-1' ({:?})", thread.id());
+    assert_eq!(lines.next(), Some(expected.as_str()));
+
+    // Created at: 2024-05-01T12:34:56.789012Z
+    let created_at = lines.next().unwrap().strip_prefix("Created at: ").unwrap();
+    assert_eq!(created_at.len(), 27);
+    let field = |range: std::ops::Range<usize>| created_at[range].parse::<i64>().unwrap();
+    let days = days_from_civil(field(0..4), field(5..7), field(8..10));
+    let secs = days * 86_400 + field(11..13) * 3600 + field(14..16) * 60 + field(17..19);
+    let micros = field(20..26);
+    let since_epoch = error
+        .created_at()
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap();
+    assert_eq!(secs, since_epoch.as_secs() as i64);
+    assert_eq!(micros, i64::from(since_epoch.subsec_micros()));
+    assert!(created_at.ends_with('Z'));
+
+    assert_eq!(lines.next(), Some(""));
+    assert_eq!(lines.next(), Some("Caused by:"));
+
+    let debug = format!("{:#?}", error);
+    assert!(debug.contains("thread: 'worker-1'"));
+    assert!(debug.contains("created_at: 2"));
+}
@@ -0,0 +1,74 @@
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::io;
+use wallee::{wallee, Context, Error, OtelValue, Result};
+
+// Stands in for an exporter that records the attributes of an exception
+// event.
+#[derive(Default)]
+struct Exporter {
+    events: Vec<BTreeMap<&'static str, OtelValue>>,
+}
+
+impl Exporter {
+    fn record(&mut self, error: &Error) {
+        self.events
+            .push(error.otel_attributes().into_iter().collect());
+    }
+}
+
+fn read_config() -> Result<String> {
+    let error = io::Error::new(io::ErrorKind::NotFound, "no such file");
+    Err(error).context("failed to read config")
+}
+
+#[test]
+fn test_attributes() {
+    let mut exporter = Exporter::default();
+    let line = line!() + 1;
+    let error = read_config().context("failed to start").unwrap_err();
+    exporter.record(&error);
+
+    let event = &exporter.events[0];
+    assert_eq!(
+        event.keys().copied().collect::<Vec<_>>(),
+        [
+            "code.column",
+            "code.filepath",
+            "code.lineno",
+            "exception.message",
+            "exception.stacktrace",
+            "exception.type",
+        ],
+    );
+    assert_eq!(
+        event["exception.type"].as_str(),
+        Some(type_name::<io::Error>())
+    );
+    assert_eq!(event["exception.message"].as_str(), Some("failed to start"));
+    assert_eq!(event["code.filepath"].as_str(), Some(file!()));
+    assert_eq!(event["code.lineno"], OtelValue::I64(line.into()));
+
+    let stacktrace = event["exception.stacktrace"].as_str().unwrap();
+    assert!(stacktrace.contains("failed to start"));
+    assert!(stacktrace.contains("Caused by:"));
+    assert!(stacktrace.contains("failed to read config"));
+    assert!(stacktrace.contains("no such file"));
+}
+
+#[test]
+fn test_message_type() {
+    let error = wallee!("oh no!").context("context");
+    let attributes = error.otel_attributes();
+    let (key, value) = &attributes[0];
+    assert_eq!(*key, "exception.type");
+    assert_eq!(value.to_string(), "&str");
+}
+
+#[test]
+fn test_shared_type() {
+    let error = Error::new(io::Error::other("oh no!")).share();
+    let error = Error::new(error).context("context");
+    let attributes = error.otel_attributes();
+    assert_eq!(attributes[0].1.as_str(), Some(type_name::<io::Error>()));
+}
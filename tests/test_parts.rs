@@ -0,0 +1,118 @@
+use std::error::Error as StdError;
+use std::io;
+use wallee::{bail, wallee, Context, Error, Result};
+
+fn open() -> Result<()> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "no such file"))?;
+    Ok(())
+}
+
+fn load() -> Result<()> {
+    bail!("failed to load");
+}
+
+#[test]
+fn test_into_parts() {
+    let (object, location, _backtrace) = open().unwrap_err().into_parts();
+    assert_eq!(location.line(), 6);
+    assert_eq!(location.file(), file!());
+
+    let error = object.downcast::<io::Error>().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_round_trip() {
+    let error = open().context("failed to open config").unwrap_err();
+    let expected = format!("{:#}", error);
+
+    let (object, location, backtrace) = error.into_parts();
+    assert_eq!(location.line(), 26);
+
+    let error = Error::from_parts(object, location, backtrace);
+    assert_eq!(error.line(), 26);
+    assert_eq!(format!("{:#}", error), expected);
+    assert_eq!(error.chain().count(), 2);
+    assert!(error.find::<io::Error>().is_some());
+}
+
+#[test]
+fn test_boxed() {
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(io::Error::other("oh no"));
+    let address = &*boxed as *const dyn StdError as *const ();
+    let error = wallee!(boxed);
+
+    let (object, _location, _backtrace) = error.into_parts();
+    assert_eq!(&*object as *const dyn StdError as *const (), address);
+}
+
+#[test]
+fn test_message() {
+    let (object, location, backtrace) = load().unwrap_err().into_parts();
+    assert_eq!(object.to_string(), "failed to load");
+    assert!(object.source().is_none());
+
+    let error = Error::from_parts(object, location, backtrace);
+    assert_eq!(error.to_string(), "failed to load");
+    assert_eq!(error.line(), 11);
+    let _ = error.backtrace();
+}
+
+#[test]
+fn test_context_round_trip() {
+    wallee::set_error_ids(true);
+    let error = wallee!("boom").context("ctx");
+    assert!(error.id().is_some());
+
+    // The object is the context, with the error it was attached to as its
+    // source. That error keeps its backtrace.
+    let (object, location, backtrace) = error.into_parts();
+    assert_eq!(object.to_string(), "ctx");
+    assert_eq!(object.source().unwrap().to_string(), "boom");
+    assert!(backtrace.is_none());
+
+    let error = Error::from_parts(object, location, backtrace);
+    assert_eq!(error.to_string(), "ctx");
+    assert_eq!(error.root_cause().to_string(), "boom");
+    assert_eq!(error.chain().count(), 2);
+    assert_eq!(error.downcast_ref::<&str>(), None);
+    assert_eq!(error.id(), None);
+    let _ = error.backtrace();
+}
+
+#[test]
+fn test_std_context_round_trip() {
+    let result: std::result::Result<(), io::Error> = Err(io::Error::other("boom"));
+    let error = result.context("ctx").unwrap_err();
+    let backtrace = error.backtrace().to_string();
+
+    // The context of a std error is held in the same layer as the backtrace,
+    // which is handed out with it.
+    let (object, location, backtrace_part) = error.into_parts();
+    assert_eq!(object.to_string(), "ctx");
+    assert_eq!(
+        backtrace_part.as_ref().map(ToString::to_string),
+        Some(backtrace.clone())
+    );
+
+    let error = Error::from_parts(object, location, backtrace_part);
+    assert_eq!(format!("{:#}", error), "ctx: boom");
+    assert_eq!(error.backtrace().to_string(), backtrace);
+    assert!(error.find::<io::Error>().is_some());
+}
+
+#[test]
+fn test_origin() {
+    wallee::set_error_ids(true);
+    let error = load().unwrap_err();
+    assert!(error.id().is_some());
+
+    // An error without context is handed out bare, and is not given an ID or
+    // the scopes of the place where it is rebuilt.
+    let (object, location, backtrace) = error.into_parts();
+    let error = wallee::scope("rebuilding", || {
+        Error::from_parts(object, location, backtrace)
+    });
+    assert_eq!(error.id(), None);
+    assert!(error.scopes().is_empty());
+}
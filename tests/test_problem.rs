@@ -0,0 +1,131 @@
+use std::io;
+use wallee::{wallee, Context, Error, HttpStatus, ProblemDetails, ProblemExtension, Result};
+
+fn load_user(id: u32) -> Result<String> {
+    let error = io::Error::new(io::ErrorKind::NotFound, "no row in table users");
+    Err(error)
+        .context(format!("user {} does not exist", id))
+        .context(HttpStatus(404))
+        .context(ProblemExtension::new("user_id", id))
+}
+
+#[test]
+fn test_client_error() {
+    let error = load_user(42).unwrap_err();
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(
+        problem,
+        ProblemDetails {
+            type_uri: "about:blank".to_owned(),
+            title: "Not Found".to_owned(),
+            status: 404,
+            detail: Some("user 42 does not exist".to_owned()),
+            instance: None,
+            extensions: vec![("user_id".to_owned(), "42".to_owned())],
+        },
+    );
+}
+
+#[test]
+fn test_chain() {
+    let error = load_user(42).unwrap_err();
+    assert_eq!(error.to_string(), "user 42 does not exist");
+    assert_eq!(
+        format!("{:#}", error),
+        "user 42 does not exist: no row in table users",
+    );
+    assert_eq!(error.chain().count(), 2);
+    assert!(!format!("{:?}", error).contains("404"));
+    assert!(error.find::<HttpStatus>().is_some());
+}
+
+#[test]
+fn test_detail_beneath_status() {
+    let error = wallee!("page must be a number")
+        .context(HttpStatus(400))
+        .context("failed to list users");
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(problem.status, 400);
+    assert_eq!(problem.detail.as_deref(), Some("page must be a number"));
+
+    let error = Err::<(), _>(io::Error::new(io::ErrorKind::NotFound, "no such user"))
+        .context(HttpStatus(404))
+        .unwrap_err();
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(problem.detail.as_deref(), Some("no such user"));
+    assert_eq!(error.to_string(), "no such user");
+}
+
+#[test]
+fn test_server_error() {
+    let error = wallee!("connection refused").context("failed to query users");
+    let problem = ProblemDetails::from(&error);
+    assert_eq!(problem.status, 500);
+    assert_eq!(problem.title, "Internal Server Error");
+    assert_eq!(problem.detail, None);
+    assert!(problem.extensions.is_empty());
+    assert_eq!(
+        problem.to_json(),
+        r#"{"type":"about:blank","title":"Internal Server Error","status":500}"#,
+    );
+}
+
+#[test]
+fn test_status_as_error() {
+    let error = Error::new(HttpStatus(429));
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(problem.status, 429);
+    assert_eq!(problem.title, "Too Many Requests");
+    assert_eq!(problem.detail, None);
+    assert_eq!(error.to_string(), "429 Too Many Requests");
+}
+
+#[test]
+fn test_outermost_wins() {
+    let error = wallee!("bad digit")
+        .context(HttpStatus(400))
+        .context(ProblemExtension::new("field", "page"))
+        .context("invalid query")
+        .context(HttpStatus(422))
+        .context(ProblemExtension::new("field", "query"))
+        .context(ProblemExtension::new("status", 200));
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(problem.status, 422);
+    assert_eq!(problem.detail.as_deref(), Some("invalid query"));
+    assert_eq!(
+        problem.extensions,
+        [("field".to_owned(), "query".to_owned())],
+    );
+}
+
+#[test]
+fn test_verbose() {
+    let line = line!() + 1;
+    let error = wallee!("connection refused").context("failed to query users");
+    let mut problem = ProblemDetails::verbose(&error);
+    problem.type_uri = "https://example.com/problems/database".to_owned();
+    problem.instance = Some("/users/42".to_owned());
+    assert_eq!(
+        problem.detail.as_deref(),
+        Some("failed to query users: connection refused"),
+    );
+
+    let location = format!("{}:{}:{}", file!(), line, error.location().column());
+    assert_eq!(
+        problem.to_json(),
+        format!(
+            r#"{{"type":"https://example.com/problems/database","title":"Internal Server Error","status":500,"detail":"failed to query users: connection refused","instance":"/users/42","location":"{}"}}"#,
+            location,
+        ),
+    );
+}
+
+#[test]
+fn test_json_escape() {
+    let error = wallee!("unexpected \"}}\"\n\tat\\end\u{1}").context(HttpStatus(400));
+    let problem = ProblemDetails::new(&error);
+    assert_eq!(
+        problem.to_json(),
+        r#"{"type":"about:blank","title":"Bad Request","status":400,"detail":"unexpected \"}\"\n\tat\\end\u0001"}"#,
+    );
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use self::common::block_on;
+use std::future;
+use std::io;
+use std::panic;
+use wallee::{bail, Error, InScope, Located, Result};
+
+fn f() -> Result<()> {
+    bail!("oh no!");
+}
+
+fn messages(error: &Error) -> Vec<&str> {
+    error.scopes().iter().map(|scope| scope.message()).collect()
+}
+
+#[test]
+fn test_nested() {
+    let line = line!() + 1;
+    let error = wallee::scope("outer", || {
+        wallee::scope(format!("inner {}", 1), || f().unwrap_err())
+    });
+    assert_eq!(messages(&error), ["outer", "inner 1"]);
+    let location = error.scopes()[0].location();
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), line);
+
+    // Errors outside of any scope record none.
+    assert!(f().unwrap_err().scopes().is_empty());
+}
+
+#[test]
+fn test_context() {
+    let error = wallee::scope("outer", f).unwrap_err();
+
+    // Context attached outside of the scope keeps the scopes of the origin.
+    let error = error.context("context");
+    assert_eq!(messages(&error), ["outer"]);
+    let (_, error) = error.pop_context().unwrap();
+    assert_eq!(messages(&error), ["outer"]);
+
+    // Context attached in another scope does not add it.
+    let error = wallee::scope("other", || error.context("context"));
+    assert_eq!(messages(&error), ["outer"]);
+}
+
+#[test]
+fn test_panic() {
+    let result = panic::catch_unwind(|| wallee::scope("panicking", || panic!("oh no!")));
+    assert!(result.is_err());
+    assert!(f().unwrap_err().scopes().is_empty());
+}
+
+#[test]
+fn test_in_scope() {
+    async fn fetch() -> Result<()> {
+        future::ready(()).await;
+        f()
+    }
+
+    let error = block_on(async {
+        let result = fetch().in_scope("fetching").await;
+        assert!(f().unwrap_err().scopes().is_empty());
+        result
+    })
+    .unwrap_err();
+    assert_eq!(messages(&error), ["fetching"]);
+}
+
+#[test]
+fn test_chain() {
+    let error = wallee::scope("outer", || wallee::scope("inner", f)).unwrap_err();
+    assert_eq!(format!("{:#}", error), "outer: inner: oh no!");
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["outer", "inner", "oh no!"]);
+
+    // Underneath the scopes, the error keeps its own location.
+    let error = error.into_root();
+    assert_eq!(error.to_string(), "oh no!");
+    assert_eq!(error.line(), 10);
+    assert!(error.scopes().is_empty());
+
+    let line = line!() + 1;
+    let located = wallee::scope("converting", || Located::new(io::Error::other("oh no!")));
+    let error = Error::from(located);
+    assert_eq!(messages(&error), ["converting"]);
+    assert_eq!(error.into_root().line(), line);
+}
+
+#[test]
+fn test_debug() {
+    let line = line!() + 1;
+    let error = wallee::scope("outer", || wallee::scope("inner", f)).unwrap_err();
+    let expected = format!(
+        "{file}({line}:17): outer\n\nCaused by:\n    0: inner\n    1: oh no!",
+        file = file!(),
+        line = line,
+    );
+    assert_eq!(&format!("{:?}", error)[..expected.len()], expected);
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "sentry")]
+
+use serde_json::Value;
+use std::backtrace::Backtrace;
+use std::io;
+use thiserror::Error;
+use wallee::{Context, Error, Result};
+
+#[derive(Error, Debug)]
+#[error("failed to parse config")]
+struct ConfigError {
+    #[source]
+    source: io::Error,
+}
+
+fn parse_config() -> Result<(), ConfigError> {
+    let source = io::Error::new(io::ErrorKind::InvalidData, "unexpected end of file");
+    Err(ConfigError { source })
+}
+
+fn load_config() -> Result<()> {
+    parse_config().context("failed to load config")
+}
+
+fn start() -> Result<()> {
+    load_config().context("failed to start")
+}
+
+// Drops the frames of the backtrace, which may or may not have been captured
+// depending on the environment, keeping the top frame of each value: the
+// location of the wallee layer that holds it.
+fn without_backtrace(mut event: Value) -> Value {
+    for value in event["exception"]["values"].as_array_mut().unwrap() {
+        let frames = value["stacktrace"]["frames"].as_array_mut().unwrap();
+        frames.drain(..frames.len() - 1);
+    }
+    event
+}
+
+fn fixture(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn test_event() {
+    let error = start().unwrap_err();
+    let event = error.to_sentry_event();
+    let event: Value = serde_json::from_str(&event).unwrap();
+    assert_eq!(
+        without_backtrace(event),
+        fixture(include_str!("fixtures/sentry_event.json"))
+    );
+}
+
+#[test]
+fn test_message() {
+    let error = wallee::wallee!("oh no!");
+    let event: Value = serde_json::from_str(&error.to_sentry_event()).unwrap();
+    let values = event["exception"]["values"].as_array().unwrap();
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0]["type"], "&str");
+    assert_eq!(values[0]["value"], "oh no!");
+}
+
+#[test]
+fn test_backtrace_frames() {
+    let error = io::Error::other("oh no!");
+    let location = wallee::Location::caller();
+    let error = Error::from_parts(Box::new(error), location, Some(Backtrace::force_capture()))
+        .context("context");
+    let event: Value = serde_json::from_str(&error.to_sentry_event()).unwrap();
+    let values = event["exception"]["values"].as_array().unwrap();
+
+    // Only the root cause gets the frames of the backtrace, below the
+    // location where the error originated.
+    let outer = values[0]["stacktrace"]["frames"].as_array().unwrap();
+    assert_eq!(outer.len(), 1);
+    let frames = values[1]["stacktrace"]["frames"].as_array().unwrap();
+    assert!(frames.len() > 1);
+    let top = frames.last().unwrap();
+    assert_eq!(top["lineno"], u64::from(location.line()));
+    assert!(frames.iter().any(|frame| {
+        frame["function"]
+            .as_str()
+            .is_some_and(|function| function.ends_with("test_backtrace_frames"))
+    }));
+}
@@ -0,0 +1,119 @@
+use std::error::Error as StdError;
+use std::io;
+use std::thread;
+use wallee::{bail, Context, Error, Result, SharedError};
+
+fn load() -> Result<()> {
+    bail!("failed to load config");
+}
+
+fn fetch() -> Result<()> {
+    Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")).context("failed to fetch")
+}
+
+#[test]
+fn test_clone() {
+    let shared = load().unwrap_err().share();
+    let clone = shared.clone();
+    assert!(SharedError::ptr_eq(&shared, &clone));
+    assert_eq!(clone.to_string(), "failed to load config");
+    assert_eq!(clone.line(), 7);
+    assert_eq!(clone.file(), file!());
+}
+
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<SharedError>();
+
+    let shared = fetch().unwrap_err().share();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || shared.root_cause().to_string())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "timed out");
+    }
+}
+
+#[test]
+fn test_std_error() {
+    let shared = fetch().unwrap_err().share();
+    let error: &dyn StdError = &shared;
+    assert_eq!(error.to_string(), "failed to fetch");
+    assert_eq!(error.source().unwrap().to_string(), "timed out");
+}
+
+#[test]
+fn test_into_error() {
+    let shared = fetch().unwrap_err().share();
+
+    // Last clone: the original error comes back.
+    let error = shared.into_error();
+    assert!(error.downcast_ref::<SharedError>().is_none());
+    assert!(error.find::<io::Error>().is_some());
+
+    let shared = fetch().unwrap_err().share();
+    let error = shared.clone().into_error();
+    assert_eq!(error.line(), 11);
+    assert_eq!(error.to_string(), "failed to fetch");
+    assert_eq!(error.chain().count(), 2);
+    assert!(error.find::<io::Error>().is_some());
+    assert!(error.downcast_ref::<SharedError>().is_some());
+}
+
+#[test]
+fn test_downcast_shared() {
+    let shared = Error::new(io::Error::new(io::ErrorKind::TimedOut, "timed out")).share();
+    let _keep = shared.clone();
+    let mut error = shared.into_error();
+    assert!(error.is::<io::Error>());
+    assert_eq!(
+        error.downcast_ref::<io::Error>().unwrap().kind(),
+        io::ErrorKind::TimedOut
+    );
+
+    // The other clone still holds on to the io::Error.
+    assert!(error.downcast_mut::<io::Error>().is_none());
+    assert!(error.find_mut::<io::Error>().is_none());
+    let error = error.downcast::<io::Error>().unwrap_err();
+    assert!(error.downcast::<SharedError>().is_ok());
+
+    let shared = fetch().unwrap_err().share();
+    let _keep = shared.clone();
+    let error = shared.into_error().context("while retrying");
+    assert!(error.is::<io::Error>());
+    assert_eq!(error.downcast_ref::<&str>(), Some(&"while retrying"));
+}
+
+#[test]
+fn test_question_mark() {
+    fn retry(shared: &SharedError) -> Result<()> {
+        Err(shared.clone())?
+    }
+
+    let shared = load().unwrap_err().share();
+    let _keep = shared.clone();
+    let error = retry(&shared).unwrap_err();
+    assert_eq!(error.line(), 7);
+
+    let error = Error::new(shared.clone());
+    assert_eq!(error.line(), 7);
+
+    let error = Err::<(), _>(shared).context("while retrying").unwrap_err();
+    assert_eq!(error.to_string(), "while retrying");
+    assert_eq!(error.root_cause().to_string(), "failed to load config");
+}
+
+#[test]
+fn test_debug() {
+    let error = fetch().unwrap_err();
+    let expected = format!("{:?}", error);
+    let shared = error.share();
+    assert_eq!(format!("{:?}", shared), expected);
+
+    let _keep = shared.clone();
+    assert_eq!(format!("{:?}", shared.into_error()), expected);
+}
@@ -0,0 +1,121 @@
+use std::io;
+use wallee::{bail, wallee, Cleanup, Context, Error, Result};
+
+fn write_rows() -> Result<()> {
+    bail!("disk full");
+}
+
+fn rollback() -> Result<()> {
+    bail!("connection reset");
+}
+
+fn unlock() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "lock is stale",
+    ))
+}
+
+#[test]
+fn test_add_suppressed() {
+    let mut error = write_rows().unwrap_err();
+    assert_eq!(error.suppressed().count(), 0);
+
+    error.add_suppressed(rollback().unwrap_err());
+    let suppressed: Vec<String> = error.suppressed().map(Error::to_string).collect();
+    assert_eq!(suppressed, ["connection reset"]);
+
+    // Suppressed errors are not part of the cause chain.
+    assert_eq!(error.chain().count(), 1);
+    assert!(error.downcast_ref::<&str>().is_some());
+}
+
+#[test]
+fn test_suppressed_under_context() {
+    let mut error = write_rows().unwrap_err();
+    error.add_suppressed(rollback().unwrap_err());
+    let mut error = error.context("failed to commit");
+    error.add_suppressed(wallee!("failed to notify"));
+
+    let suppressed: Vec<String> = error.suppressed().map(Error::to_string).collect();
+    assert_eq!(suppressed, ["failed to notify", "connection reset"]);
+}
+
+#[test]
+fn test_or_cleanup() {
+    let (result, line) = (write_rows().or_cleanup(unlock), line!());
+    let error = result.unwrap_err();
+    assert_eq!(error.to_string(), "disk full");
+
+    let suppressed = error.suppressed().next().unwrap();
+    assert_eq!(suppressed.to_string(), "lock is stale");
+    assert_eq!(suppressed.file(), file!());
+    assert_eq!(suppressed.line(), line);
+}
+
+#[test]
+fn test_or_cleanup_succeeds() {
+    let error = write_rows().or_cleanup(|| Ok::<(), Error>(())).unwrap_err();
+    assert_eq!(error.suppressed().count(), 0);
+
+    let mut ran = false;
+    let value = Ok::<_, Error>(1)
+        .or_cleanup(|| {
+            ran = true;
+            rollback()
+        })
+        .unwrap();
+    assert_eq!(value, 1);
+    assert!(!ran);
+}
+
+#[test]
+fn test_or_cleanup_std_error() {
+    let result: io::Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "missing"));
+    let (error, line) = (result.or_cleanup(rollback).unwrap_err(), line!());
+    assert_eq!(error.line(), line);
+    assert!(error.is::<io::Error>());
+    assert_eq!(error.suppressed().count(), 1);
+}
+
+#[test]
+fn test_debug() {
+    let error = write_rows()
+        .context("failed to commit")
+        .or_cleanup(rollback)
+        .unwrap_err();
+
+    let expected = "\
+tests/test_suppressed.rs(84:10): failed to commit
+
+Caused by:
+    disk full
+
+Suppressed:
+    tests/test_suppressed.rs(9:5): connection reset";
+    assert_eq!(expected, &format!("{:?}", error)[..expected.len()]);
+
+    let mut error = error;
+    error.add_suppressed(wallee!("failed to notify").context("shutdown"));
+    let expected = "
+Suppressed:
+    0: tests/test_suppressed.rs(9:5): connection reset
+    1: tests/test_suppressed.rs(99:54): shutdown: failed to notify";
+    assert!(format!("{:?}", error).contains(expected));
+}
+
+#[test]
+fn test_alternate_debug() {
+    let mut error = write_rows().unwrap_err();
+    error.add_suppressed(rollback().unwrap_err());
+    let mut error = error.context("failed to commit");
+    error.add_suppressed(wallee!("failed to notify"));
+
+    // Both formats list what was suppressed on every layer.
+    let debug = format!("{:#?}", error);
+    assert!(debug.contains("failed to notify"));
+    assert!(debug.contains("connection reset"));
+    let debug = format!("{:?}", error);
+    assert!(debug.contains("failed to notify"));
+    assert!(debug.contains("connection reset"));
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_error::ErrorLayer;
+use tracing_subscriber::layer::{Context as LayerContext, Layer, SubscriberExt};
+use wallee::{bail, Context, Result, TraceErr};
+
+type Events = Arc<Mutex<Vec<(Level, BTreeMap<String, String>)>>>;
+
+struct Recorder(Events);
+
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+}
+
+impl<S: Subscriber> Layer<S> for Recorder {
+    fn on_event(&self, event: &Event, _: LayerContext<S>) {
+        let mut fields = BTreeMap::new();
+        event.record(&mut Fields(&mut fields));
+        let level = *event.metadata().level();
+        self.0.lock().unwrap().push((level, fields));
+    }
+}
+
+fn with_subscriber<R>(f: impl FnOnce() -> R) -> (R, Events) {
+    let events = Events::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(ErrorLayer::default())
+        .with(Recorder(events.clone()));
+    let result = tracing::subscriber::with_default(subscriber, f);
+    (result, events)
+}
+
+fn load_user(id: u64) -> Result<()> {
+    let _span = tracing::info_span!("load_user", id).entered();
+    bail!("no such row");
+}
+
+fn handle_request() -> Result<()> {
+    let _span = tracing::info_span!("request", path = "/users/7").entered();
+    load_user(7).context("failed to handle request")
+}
+
+#[test]
+fn test_span_trace() {
+    let (error, _) = with_subscriber(|| handle_request().unwrap_err());
+    let span_trace = error.span_trace().unwrap();
+
+    let mut spans = Vec::new();
+    span_trace.with_spans(|metadata, fields| {
+        spans.push(format!("{}{{{}}}", metadata.name(), fields));
+        true
+    });
+    assert_eq!(spans, ["load_user{id=7}", "request{path=\"/users/7\"}"]);
+
+    let debug = format!("{:?}", error);
+    assert!(
+        debug.contains("\n\nSpan trace:\n   0: test_tracing::load_user\n           with id=7\n")
+    );
+}
+
+#[test]
+fn test_no_span_trace() {
+    let error = handle_request().unwrap_err();
+    assert!(!format!("{:?}", error).contains("Span trace:"));
+}
+
+#[test]
+fn test_emit() {
+    let (line, events) = with_subscriber(|| {
+        let (result, line) = (load_user(7).context("failed to handle request"), line!());
+        assert!(result.trace_err().is_err());
+        assert!(Ok::<(), wallee::Error>(()).trace_err().is_ok());
+        line
+    });
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let (level, fields) = &events[0];
+    assert_eq!(*level, Level::ERROR);
+    assert_eq!(fields["message"], "failed to handle request");
+    assert_eq!(
+        fields["error.chain"],
+        r#"["failed to handle request", "no such row"]"#,
+    );
+    assert_eq!(fields["error.file"], file!());
+    assert_eq!(fields["error.line"], line.to_string());
+}
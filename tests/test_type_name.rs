@@ -0,0 +1,88 @@
+use std::any::type_name;
+use std::io;
+use thiserror::Error;
+use wallee::{wallee, Context, Error, Result};
+
+#[derive(Error, Debug)]
+#[error("failed to parse config")]
+struct ConfigError {
+    #[source]
+    source: io::Error,
+}
+
+fn parse_config() -> Result<(), ConfigError> {
+    let source = io::Error::new(io::ErrorKind::InvalidData, "unexpected end of file");
+    Err(ConfigError { source })
+}
+
+fn load_config() -> Result<()> {
+    parse_config().context("failed to load config")
+}
+
+fn start() -> Result<()> {
+    load_config().context(String::from("failed to start"))
+}
+
+#[test]
+fn test_type_name() {
+    assert_eq!(wallee!("oh no!").type_name(), Some("&str"));
+    assert_eq!(
+        Error::new(io::Error::other("oh no!")).type_name(),
+        Some(type_name::<io::Error>()),
+    );
+    assert_eq!(
+        parse_config().map_err(Error::new).unwrap_err().type_name(),
+        Some("test_type_name::ConfigError"),
+    );
+    assert_eq!(load_config().unwrap_err().type_name(), Some("&str"));
+    assert_eq!(
+        start().unwrap_err().type_name(),
+        Some("alloc::string::String"),
+    );
+}
+
+#[test]
+fn test_shared() {
+    let error = Error::new(Error::new(io::Error::other("oh no!")).share());
+    assert_eq!(error.type_name(), Some(type_name::<io::Error>()));
+    assert_eq!(error.typed_chain().len(), error.chain().len());
+}
+
+#[test]
+fn test_typed_chain() {
+    let error = start().unwrap_err();
+    let chain: Vec<(Option<&str>, String)> = error
+        .typed_chain()
+        .map(|(type_name, error)| (type_name, error.to_string()))
+        .collect();
+    assert_eq!(
+        chain,
+        [
+            (Some("alloc::string::String"), "failed to start".to_owned()),
+            (Some("&str"), "failed to load config".to_owned()),
+            (
+                Some("test_type_name::ConfigError"),
+                "failed to parse config".to_owned(),
+            ),
+            (None, "unexpected end of file".to_owned()),
+        ],
+    );
+    assert_eq!(error.typed_chain().len(), error.chain().len());
+    assert_eq!(error.typed_chain().next_back().unwrap().0, None);
+}
+
+#[test]
+fn test_debug() {
+    wallee::set_debug_type_names(true);
+    let error = start().unwrap_err();
+    let expected = "\
+tests/test_type_name.rs(23:19): failed to start [alloc::string::String]
+
+Caused by:
+    0: failed to load config [&str]
+    1: failed to parse config [test_type_name::ConfigError]
+    2: unexpected end of file";
+    assert_eq!(expected, &format!("{:?}", error)[..expected.len()]);
+    wallee::set_debug_type_names(false);
+    assert!(!format!("{:?}", error).contains("[&str]"));
+}
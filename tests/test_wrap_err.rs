@@ -0,0 +1,119 @@
+use std::borrow::Cow;
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::io;
+use wallee::{Error, Result, WrapErr};
+
+#[test]
+fn test_str() {
+    let result: Result<(), &str> = Err("oh no!");
+    let line = line!() + 1;
+    let error = result.wrap_err("context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: oh no!");
+    assert_eq!(error.line(), line);
+    assert_eq!(error.root_cause().to_string(), "oh no!");
+}
+
+#[test]
+fn test_string() {
+    let result: Result<(), String> = Err(format!("invalid port {}", 0));
+    let error = result.wrap_err_with(|| "context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: invalid port 0");
+
+    let result: Result<(), Cow<str>> = Err(Cow::Borrowed("oh no!"));
+    let error = result.wrap_err("context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: oh no!");
+}
+
+#[test]
+fn test_ok() {
+    let result: Result<u32, String> = Ok(1);
+    let value = result
+        .wrap_err_with(|| -> &str { panic!("not called") })
+        .unwrap();
+    assert_eq!(value, 1);
+}
+
+#[test]
+fn test_boxed() {
+    let inner = io::Error::other("oh no!");
+    let outer = wallee::wallee!(inner).context("outer");
+    let boxed: Box<dyn StdError + Send + Sync> = outer.into();
+    let result: Result<(), _> = Err(boxed);
+    let error = result.wrap_err("context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: outer: oh no!");
+    assert!(error.root_cause().is::<io::Error>());
+}
+
+#[test]
+fn test_boxed_local() {
+    let inner = io::Error::other("oh no!");
+    let boxed: Box<dyn StdError> = Box::new(inner);
+    let result: Result<(), _> = Err(boxed);
+    let error = result.wrap_err("context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: oh no!");
+    assert_eq!(error.chain().count(), 2);
+}
+
+#[derive(Debug)]
+struct Outer {
+    source: io::Error,
+}
+
+impl Display for Outer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("outer")
+    }
+}
+
+impl StdError for Outer {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn io_error() -> io::Error {
+    let source = io::Error::other("inner");
+    io::Error::other(Outer { source })
+}
+
+#[test]
+fn test_boxed_chain() {
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(io_error());
+    let result: Result<(), _> = Err(boxed);
+    let error = result.wrap_err("ctx").unwrap_err();
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["ctx", "outer", "inner"]);
+    assert!(error.root_cause().is::<io::Error>());
+
+    let boxed: Box<dyn StdError> = Box::new(io_error());
+    let result: Result<(), _> = Err(boxed);
+    let error = result.wrap_err("ctx").unwrap_err();
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["ctx", "outer", "inner"]);
+}
+
+#[test]
+fn test_display() {
+    #[derive(Debug)]
+    struct Code(u32);
+
+    impl Display for Code {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "code {}", self.0)
+        }
+    }
+
+    let result: Result<(), Code> = Err(Code(7));
+    let error = result.map_err(Error::msg).wrap_err("context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: code 7");
+    assert_eq!(error.root_cause().to_string(), "code 7");
+}
+
+#[test]
+fn test_error() {
+    let result: Result<()> = Err(wallee::wallee!("oh no!"));
+    let error = result.wrap_err("context").unwrap_err();
+    assert_eq!(format!("{:#}", error), "context: oh no!");
+    assert_eq!(error.chain().count(), 2);
+}